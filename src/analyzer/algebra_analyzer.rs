@@ -45,7 +45,9 @@ pub(crate) fn check_for_paradox(
                 c.iter().any(|c| {
                     matches!(
                         c.1,
-                        Assertion::DontIgnoreTaints | Assertion::DontRemoveTaints(..),
+                        Assertion::DontIgnoreTaints
+                            | Assertion::DontRemoveTaints(..)
+                            | Assertion::FunctionDoesNotExist(..),
                     )
                 })
             })
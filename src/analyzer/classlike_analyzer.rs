@@ -6,6 +6,7 @@ use crate::scope_analyzer::ScopeAnalyzer;
 use crate::scope::BlockContext;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt_analyzer::AnalysisError;
+use hakana_reflection_info::classlike_info::ClassLikeInfo;
 use hakana_reflection_info::codebase_info::symbols::SymbolKind;
 use hakana_reflection_info::data_flow::graph::DataFlowGraph;
 use hakana_reflection_info::function_context::{FunctionContext, FunctionLikeIdentifier};
@@ -143,6 +144,20 @@ impl<'a> ClassLikeAnalyzer<'a> {
             );
         }
 
+        if statements_analyzer
+            .get_config()
+            .find_missing_override_attributes
+        {
+            for method in &stmt.methods {
+                check_override_attribute(
+                    &mut analysis_data,
+                    statements_analyzer,
+                    classlike_storage,
+                    method,
+                );
+            }
+        }
+
         let mut existing_enum_str_values = FxHashMap::default();
         let mut existing_enum_int_values = FxHashMap::default();
 
@@ -256,3 +271,73 @@ fn emit_dupe_enum_case_issue(
         statements_analyzer.get_file_path_actual(),
     );
 }
+
+fn check_override_attribute(
+    analysis_data: &mut FunctionAnalysisData,
+    statements_analyzer: &StatementsAnalyzer,
+    classlike_storage: &ClassLikeInfo,
+    method: &aast::Method_<(), ()>,
+) {
+    let codebase = statements_analyzer.get_codebase();
+    let interner = statements_analyzer.get_interner();
+
+    let method_name = if let Some(method_name) = interner.get(&method.name.1) {
+        method_name
+    } else {
+        return;
+    };
+
+    // constructors don't take the attribute
+    if method_name == StrId::CONSTRUCT {
+        return;
+    }
+
+    let functionlike_storage = if let Some(functionlike_storage) = codebase
+        .functionlike_infos
+        .get(&(classlike_storage.name, method_name))
+    {
+        functionlike_storage
+    } else {
+        return;
+    };
+
+    if functionlike_storage.overriding {
+        return;
+    }
+
+    let overridden_classlike = classlike_storage
+        .overridden_method_ids
+        .get(&method_name)
+        .and_then(|overridden_classlikes| {
+            overridden_classlikes.iter().find(|overridden_classlike| {
+                statements_analyzer
+                    .get_config()
+                    .require_override_for_interface_methods
+                    || !codebase.interface_exists(overridden_classlike)
+            })
+        });
+
+    if let Some(overridden_classlike) = overridden_classlike {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::MissingOverrideAttribute,
+                format!(
+                    "Method {}::{} overrides {}::{} but is missing the <<__Override>> attribute",
+                    interner.lookup(&classlike_storage.name),
+                    interner.lookup(&method_name),
+                    interner.lookup(overridden_classlike),
+                    interner.lookup(&method_name),
+                ),
+                functionlike_storage
+                    .name_location
+                    .unwrap_or(functionlike_storage.def_location),
+                &Some(FunctionLikeIdentifier::Method(
+                    classlike_storage.name,
+                    method_name,
+                )),
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
+}
@@ -18,6 +18,32 @@ pub struct JsonConfig {
     pub allowed_issues: Vec<String>,
     #[serde(default)]
     pub test_files: Vec<String>,
+    #[serde(default)]
+    pub analyze_exclude_files: Vec<String>,
+    #[serde(default)]
+    pub widen_loop_isset_mixed: bool,
+    #[serde(default)]
+    pub strict_string_concatenation: bool,
+    #[serde(default)]
+    pub find_invalid_concat_operands: bool,
+    #[serde(default)]
+    pub find_redundant_boolean_comparisons: bool,
+    #[serde(default)]
+    pub find_redundant_defaults: bool,
+    #[serde(default)]
+    pub find_redundant_casts: bool,
+    #[serde(default)]
+    pub report_unresolved_templates: bool,
+    #[serde(default)]
+    pub find_missing_override_attributes: bool,
+    #[serde(default)]
+    pub require_override_for_interface_methods: bool,
+    #[serde(default)]
+    pub find_unused_method_parameters: bool,
+    #[serde(default)]
+    pub find_unsafe_instantiations: bool,
+    #[serde(default)]
+    pub disable_taint_analysis: bool,
 }
 
 #[derive(Deserialize, Debug, Default)]
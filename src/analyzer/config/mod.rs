@@ -1,4 +1,4 @@
-use std::{error::Error, path::Path};
+use std::{error::Error, fmt, path::Path, sync::Mutex};
 
 use hakana_reflection_info::{
     data_flow::{graph::GraphKind, tainted_node::TaintedNode},
@@ -24,6 +24,7 @@ pub struct Config {
     pub graph_kind: GraphKind,
     pub ignore_files: Vec<String>,
     pub test_files: Vec<glob::Pattern>,
+    pub analyze_exclude_files: Vec<glob::Pattern>,
     pub ignore_issue_patterns: FxHashMap<IssueKind, Vec<glob::Pattern>>,
     pub ignore_all_issues_in_patterns: Vec<glob::Pattern>,
     pub banned_builtin_functions: FxHashMap<StrId, StrId>,
@@ -31,10 +32,46 @@ pub struct Config {
     pub root_dir: String,
     pub hooks: Vec<Box<dyn CustomHook>>,
     pub ignore_mixed_issues: bool,
+    pub widen_loop_isset_mixed: bool,
+    pub strict_string_concatenation: bool,
+    pub find_invalid_concat_operands: bool,
+    pub find_redundant_boolean_comparisons: bool,
+    pub find_redundant_defaults: bool,
+    pub find_redundant_casts: bool,
+    pub report_unresolved_templates: bool,
+    pub find_missing_override_attributes: bool,
+    pub require_override_for_interface_methods: bool,
+    pub find_unused_method_parameters: bool,
+    pub find_unsafe_instantiations: bool,
     pub add_fixmes: bool,
     pub remove_fixmes: bool,
     pub all_custom_issues: FxHashSet<String>,
     pub ast_diff: bool,
+    pub collect_type_coverage: bool,
+    pub issue_callback: Option<IssueCallback>,
+}
+
+/// Receives issues while analysis is still running, so that tools can display them
+/// (or bail out) early. Issues are passed once their file has been fully analyzed,
+/// so anything suppressed by a fixme or filtered by the config is never seen here.
+/// When analyzing incrementally, issues loaded from the cache are passed before
+/// analysis starts.
+pub struct IssueCallback(Mutex<Box<dyn FnMut(&Issue) + Send>>);
+
+impl IssueCallback {
+    pub fn new(callback: impl FnMut(&Issue) + Send + 'static) -> Self {
+        Self(Mutex::new(Box::new(callback)))
+    }
+
+    pub fn call(&self, issue: &Issue) {
+        (self.0.lock().unwrap())(issue)
+    }
+}
+
+impl fmt::Debug for IssueCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IssueCallback")
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -42,6 +79,8 @@ pub struct SecurityConfig {
     ignore_patterns: Vec<glob::Pattern>,
     ignore_sink_files: FxHashMap<String, Vec<glob::Pattern>>,
     pub max_depth: u8,
+    // When false security-check fails before building the taint graph
+    pub enabled: bool,
 }
 
 impl Default for SecurityConfig {
@@ -56,6 +95,7 @@ impl SecurityConfig {
             ignore_patterns: Vec::new(),
             ignore_sink_files: FxHashMap::default(),
             max_depth: 40,
+            enabled: true,
         }
     }
 }
@@ -67,11 +107,23 @@ impl Config {
             find_unused_expressions: false,
             find_unused_definitions: false,
             ignore_mixed_issues: false,
+            widen_loop_isset_mixed: false,
+            strict_string_concatenation: false,
+            find_invalid_concat_operands: false,
+            find_redundant_boolean_comparisons: false,
+            find_redundant_defaults: false,
+            find_redundant_casts: false,
+            report_unresolved_templates: false,
+            find_missing_override_attributes: false,
+            require_override_for_interface_methods: false,
+            find_unused_method_parameters: false,
+            find_unsafe_instantiations: false,
             allowed_issues: None,
             migration_symbols: FxHashMap::default(),
             graph_kind: GraphKind::FunctionBody,
             ignore_files: Vec::new(),
             test_files: Vec::new(),
+            analyze_exclude_files: Vec::new(),
             ignore_issue_patterns: FxHashMap::default(),
             ignore_all_issues_in_patterns: vec![],
             security_config: SecurityConfig::new(),
@@ -81,6 +133,8 @@ impl Config {
             remove_fixmes: false,
             all_custom_issues,
             ast_diff: false,
+            collect_type_coverage: false,
+            issue_callback: None,
             in_migration: false,
             in_codegen: false,
             banned_builtin_functions: FxHashMap::default(),
@@ -107,6 +161,12 @@ impl Config {
             .map(|v| glob::Pattern::new(&format!("{}/{}", cwd, v)).unwrap())
             .collect();
 
+        self.analyze_exclude_files = json_config
+            .analyze_exclude_files
+            .into_iter()
+            .map(|v| glob::Pattern::new(&format!("{}/{}", cwd, v)).unwrap())
+            .collect();
+
         self.ignore_issue_patterns = json_config
             .ignore_issue_files
             .iter()
@@ -168,6 +228,21 @@ impl Config {
             })
             .collect();
 
+        self.security_config.enabled = !json_config.disable_taint_analysis;
+
+        self.widen_loop_isset_mixed = json_config.widen_loop_isset_mixed;
+        self.strict_string_concatenation = json_config.strict_string_concatenation;
+        self.find_invalid_concat_operands = json_config.find_invalid_concat_operands;
+        self.find_redundant_boolean_comparisons = json_config.find_redundant_boolean_comparisons;
+        self.find_redundant_defaults = json_config.find_redundant_defaults;
+        self.find_redundant_casts = json_config.find_redundant_casts;
+        self.report_unresolved_templates = json_config.report_unresolved_templates;
+        self.find_missing_override_attributes = json_config.find_missing_override_attributes;
+        self.require_override_for_interface_methods =
+            json_config.require_override_for_interface_methods;
+        self.find_unused_method_parameters = json_config.find_unused_method_parameters;
+        self.find_unsafe_instantiations = json_config.find_unsafe_instantiations;
+
         Ok(())
     }
 
@@ -191,6 +266,22 @@ impl Config {
         true
     }
 
+    // Excluded files are still scanned, so their symbols are known. In whole-program
+    // analysis they're analyzed too, so that taints can flow through them.
+    pub fn allow_analysis_in_file(&self, file: &str) -> bool {
+        if let GraphKind::WholeProgram(_) = self.graph_kind {
+            return true;
+        }
+
+        for exclude_pattern in &self.analyze_exclude_files {
+            if exclude_pattern.matches(file) {
+                return false;
+            }
+        }
+
+        true
+    }
+
     pub fn allow_issue_kind_in_file(&self, issue_kind: &IssueKind, file: &str) -> bool {
         if let Some(issue_entries) = self.ignore_issue_patterns.get(issue_kind) {
             for ignore_file_pattern in issue_entries {
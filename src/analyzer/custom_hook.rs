@@ -4,6 +4,8 @@ use hakana_reflection_info::{
     codebase_info::CodebaseInfo, functionlike_info::FunctionLikeInfo, t_union::TUnion,
 };
 use hakana_str::{Interner, StrId};
+
+pub use hakana_reflector::type_hint_hook::TypeHintHook;
 use oxidized::{
     aast,
     ast_defs::{self, Pos},
@@ -154,6 +156,11 @@ pub trait InternalHook {
         false
     }
 
+    // Hooks that rewrite signature types while files are being scanned
+    fn get_type_hint_hook(&self) -> Option<&dyn TypeHintHook> {
+        None
+    }
+
     #[allow(unused_variables)]
     fn after_populate(&self, codebase: &CodebaseInfo, interner: &Interner, config: &Config) {}
 }
@@ -14,6 +14,7 @@ use hakana_reflection_info::{
 };
 use hakana_reflector::typehint_resolver::get_type_from_hint;
 use hakana_str::StrId;
+use hakana_type::get_types_for_gettype_name;
 use hakana_type::type_comparator::type_comparison_result::TypeComparisonResult;
 use hakana_type::type_comparator::union_type_comparator;
 use hakana_type::type_expander::{self, TypeExpansionOptions};
@@ -420,6 +421,20 @@ fn scrape_equality_assertions(
         return get_false_equality_assertions(bop, left, right, assertion_context, false_position);
     }
 
+    if let Some((var_name, gettype_types)) =
+        get_gettype_comparison(left, right, analysis_data, assertion_context)
+    {
+        let mut if_types = FxHashMap::default();
+        if_types.insert(
+            var_name,
+            vec![gettype_types
+                .into_iter()
+                .map(Assertion::IsType)
+                .collect::<Vec<Assertion>>()],
+        );
+        return vec![if_types];
+    }
+
     if let Some(typed_value_position) =
         has_typed_value_comparison(left, right, analysis_data, assertion_context)
     {
@@ -458,6 +473,20 @@ fn scrape_inequality_assertions(
 
     // if let Some(false_position) = false_position {}
 
+    if let Some((var_name, gettype_types)) =
+        get_gettype_comparison(left, right, analysis_data, assertion_context)
+    {
+        let mut if_types = FxHashMap::default();
+        if_types.insert(
+            var_name,
+            gettype_types
+                .into_iter()
+                .map(|atomic| vec![Assertion::IsNotType(atomic)])
+                .collect(),
+        );
+        return vec![if_types];
+    }
+
     if let Some(typed_value_position) =
         has_typed_value_comparison(left, right, analysis_data, assertion_context)
     {
@@ -676,6 +705,42 @@ pub(crate) fn has_false_variable(
     None
 }
 
+// matches gettype($foo) === 'integer'
+fn get_gettype_comparison(
+    left: &aast::Expr<(), ()>,
+    right: &aast::Expr<(), ()>,
+    analysis_data: &FunctionAnalysisData,
+    assertion_context: &AssertionContext,
+) -> Option<(String, Vec<TAtomic>)> {
+    let (call, other_expr) = match (&left.2, &right.2) {
+        (aast::Expr_::Call(call), _) => (call, right),
+        (_, aast::Expr_::Call(call)) => (call, left),
+        _ => return None,
+    };
+
+    let (_, interner) = assertion_context.codebase?;
+
+    if !matches!(
+        get_static_functionlike_id_from_call(call, interner, assertion_context.resolved_names),
+        Some(FunctionLikeIdentifier::Function(StrId::GETTYPE))
+    ) {
+        return None;
+    }
+
+    let var_name = get_var_id(
+        &call.args.first()?.1,
+        assertion_context.this_class_name,
+        assertion_context.resolved_names,
+        assertion_context.codebase,
+    )?;
+
+    let type_name = analysis_data
+        .get_expr_type(other_expr.pos())?
+        .get_single_literal_string_value()?;
+
+    Some((var_name, get_types_for_gettype_name(&type_name)?))
+}
+
 pub(crate) fn has_typed_value_comparison(
     left: &aast::Expr<(), ()>,
     right: &aast::Expr<(), ()>,
@@ -362,11 +362,14 @@ fn analyze_list_assignment(
             )),
         );
 
-        if list_var_id.unwrap_or("".to_string()) == "$_" {
+        if list_var_id.unwrap_or("".to_string()) == "$_"
+            || matches!(assign_var_item.2, aast::Expr_::Omitted)
+        {
             continue;
         }
 
         let mut value_type = get_nothing();
+        let mut has_missing_offset = false;
 
         for assign_value_atomic_type in &assign_value_type.types {
             let atomic_value_type = if let TAtomic::TVec {
@@ -392,9 +395,17 @@ fn analyze_list_assignment(
 
                         value_type.clone()
                     } else {
+                        if type_param.is_nothing() {
+                            has_missing_offset = true;
+                        }
+
                         (**type_param).clone()
                     }
                 } else {
+                    if type_param.is_nothing() {
+                        has_missing_offset = true;
+                    }
+
                     (**type_param).clone()
                 }
             } else if let TAtomic::TNamedObject {
@@ -419,6 +430,20 @@ fn analyze_list_assignment(
             value_type = add_union_type(value_type, &atomic_value_type, codebase, false);
         }
 
+        // the tuple being destructured is shorter than the list
+        if has_missing_offset {
+            analysis_data.maybe_add_issue(
+                Issue::new(
+                    IssueKind::UndefinedIntArrayOffset,
+                    format!("Undefined offset {} in list assignment", offset),
+                    statements_analyzer.get_hpos(&assign_var_item.1),
+                    &context.function_context.calling_functionlike_id,
+                ),
+                statements_analyzer.get_config(),
+                statements_analyzer.get_file_path_actual(),
+            );
+        }
+
         if let Some(source_expr) = source_expr {
             let source_expr_id = expression_identifier::get_var_id(
                 source_expr,
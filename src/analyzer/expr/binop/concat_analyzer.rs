@@ -1,13 +1,17 @@
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::scope::BlockContext;
+use crate::scope_analyzer::ScopeAnalyzer;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::{expression_analyzer, stmt_analyzer::AnalysisError};
+use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_union::TUnion;
 use hakana_reflection_info::{
     data_flow::{node::DataFlowNode, path::PathKind},
     t_atomic::TAtomic,
     taint::SinkType,
 };
+use hakana_str::StrId;
 use hakana_type::{get_literal_string, get_string, wrap_atomic};
 use oxidized::aast;
 
@@ -30,6 +34,13 @@ pub(crate) fn analyze<'expr>(
             context,
             &mut None,
         )?;
+
+        if statements_analyzer
+            .get_config()
+            .find_invalid_concat_operands
+        {
+            check_concat_operand(statements_analyzer, concat_node, analysis_data, context);
+        }
     }
 
     let result_type =
@@ -42,6 +53,78 @@ pub(crate) fn analyze<'expr>(
     Ok(())
 }
 
+fn check_concat_operand(
+    statements_analyzer: &StatementsAnalyzer,
+    concat_node: &aast::Expr<(), ()>,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+) {
+    let operand_type = if let Some(operand_type) = analysis_data.get_expr_type(concat_node.pos()) {
+        operand_type
+    } else {
+        return;
+    };
+
+    let codebase = statements_analyzer.get_codebase();
+    let allow_null = !statements_analyzer.get_config().strict_string_concatenation;
+
+    if operand_type
+        .types
+        .iter()
+        .all(|atomic| is_valid_concat_operand(atomic, codebase, allow_null))
+    {
+        return;
+    }
+
+    let operand_type_id = operand_type.get_id(Some(statements_analyzer.get_interner()));
+
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            IssueKind::InvalidConcatOperand,
+            format!(
+                "Cannot concatenate value of type {}, expecting string, int, float or Stringish",
+                operand_type_id
+            ),
+            statements_analyzer.get_hpos(concat_node.pos()),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
+
+fn is_valid_concat_operand(atomic: &TAtomic, codebase: &CodebaseInfo, allow_null: bool) -> bool {
+    match atomic {
+        TAtomic::TNull => allow_null,
+        // objects implementing __toString are Stringish
+        TAtomic::TNamedObject { name, .. } => codebase.method_exists(name, &StrId::TO_STRING),
+        TAtomic::TGenericParam { as_type, .. } | TAtomic::TClassTypeConstant { as_type, .. } => {
+            as_type
+                .types
+                .iter()
+                .all(|atomic| is_valid_concat_operand(atomic, codebase, allow_null))
+        }
+        TAtomic::TTypeAlias {
+            as_type: Some(as_type),
+            ..
+        } => as_type
+            .types
+            .iter()
+            .all(|atomic| is_valid_concat_operand(atomic, codebase, allow_null)),
+        TAtomic::TVec { .. }
+        | TAtomic::TDict { .. }
+        | TAtomic::TKeyset { .. }
+        | TAtomic::TBool
+        | TAtomic::TTrue
+        | TAtomic::TFalse
+        | TAtomic::TClosure { .. }
+        | TAtomic::TClosureAlias { .. }
+        | TAtomic::TAwaitable { .. }
+        | TAtomic::TResource => false,
+        _ => true,
+    }
+}
+
 pub(crate) fn analyze_concat_nodes(
     concat_nodes: Vec<&aast::Expr<(), ()>>,
     statements_analyzer: &StatementsAnalyzer<'_>,
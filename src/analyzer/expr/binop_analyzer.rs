@@ -7,6 +7,7 @@ use crate::stmt_analyzer::AnalysisError;
 
 use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
+use hakana_reflection_info::t_union::TUnion;
 use hakana_type::type_comparator::union_type_comparator;
 use hakana_type::{get_bool, get_int};
 use oxidized::pos::Pos;
@@ -163,6 +164,21 @@ pub(crate) fn analyze(
                         statements_analyzer.get_config(),
                         statements_analyzer.get_file_path_actual(),
                     );
+                } else if matches!(
+                    expr.0,
+                    oxidized::ast_defs::Bop::Eqeqeq | oxidized::ast_defs::Bop::Diff2
+                ) && statements_analyzer
+                    .get_config()
+                    .find_redundant_boolean_comparisons
+                {
+                    check_redundant_boolean_comparison(
+                        statements_analyzer,
+                        expr,
+                        lhs_type,
+                        pos,
+                        analysis_data,
+                        context,
+                    );
                 }
             }
 
@@ -254,6 +270,56 @@ pub(crate) fn analyze(
     }
 }
 
+// `$b === true` where `$b` is already a bool (nullable bools are left alone,
+// since there the comparison is meaningful)
+fn check_redundant_boolean_comparison(
+    statements_analyzer: &StatementsAnalyzer,
+    expr: (&ast::Bop, &aast::Expr<(), ()>, &aast::Expr<(), ()>),
+    lhs_type: &TUnion,
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+) {
+    let rhs_value = match expr.2 .2 {
+        aast::Expr_::True => true,
+        aast::Expr_::False => false,
+        _ => return,
+    };
+
+    let message = if lhs_type.is_true() || lhs_type.is_false() {
+        let is_identical = lhs_type.is_true() == rhs_value;
+
+        format!(
+            "Type {} compared to {} always evaluates to {}",
+            lhs_type.get_id(Some(statements_analyzer.get_interner())),
+            rhs_value,
+            if matches!(expr.0, oxidized::ast_defs::Bop::Eqeqeq) {
+                is_identical
+            } else {
+                !is_identical
+            }
+        )
+    } else if lhs_type.is_bool() {
+        format!(
+            "Comparing a bool to {} is redundant, use the value directly",
+            rhs_value
+        )
+    } else {
+        return;
+    };
+
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            IssueKind::RedundantBooleanComparison,
+            message,
+            statements_analyzer.get_hpos(pos),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
+
 fn is_resolvable(expr: &aast::Expr<(), ()>) -> bool {
     matches!(expr.2, aast::Expr_::Lvar(_) | aast::Expr_::ObjGet(_))
 }
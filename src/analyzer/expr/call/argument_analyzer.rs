@@ -629,7 +629,11 @@ fn add_dataflow(
                         ),
                         argument_offset,
                         Some(statements_analyzer.get_hpos(input_expr.pos())),
-                        None,
+                        if specialize_taint {
+                            Some(function_call_hpos)
+                        } else {
+                            None
+                        },
                     );
 
                     data_flow_graph.add_node(new_sink.clone());
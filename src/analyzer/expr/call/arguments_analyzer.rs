@@ -25,16 +25,22 @@ use hakana_reflection_info::codebase_info::CodebaseInfo;
 use hakana_reflection_info::data_flow::graph::GraphKind;
 use hakana_reflection_info::functionlike_identifier::FunctionLikeIdentifier;
 use hakana_reflection_info::functionlike_info::{FnEffect, FunctionLikeInfo};
-use hakana_reflection_info::functionlike_parameter::{DefaultType, FunctionLikeParameter};
+use hakana_reflection_info::functionlike_parameter::{
+    DefaultType, FnParameter, FunctionLikeParameter,
+};
+use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_reflection_info::t_union::{populate_union_type, TUnion};
 use hakana_reflector::typehint_resolver::get_type_from_hint;
 use hakana_type::template::{
     self, inferred_type_replacer, standin_type_replacer, TemplateBound, TemplateResult,
 };
+use hakana_type::type_comparator::type_comparison_result::TypeComparisonResult;
+use hakana_type::type_comparator::union_type_comparator;
 use hakana_type::type_expander::{self, StaticClassType, TypeExpansionOptions};
 use hakana_type::{
-    add_optional_union_type, combine_optional_union_types, get_arraykey, get_mixed_any, wrap_atomic,
+    add_optional_union_type, combine_optional_union_types, get_arrayish_params, get_arraykey,
+    get_bool, get_float, get_int, get_mixed_any, get_null, get_string, wrap_atomic,
 };
 use indexmap::IndexMap;
 use oxidized::ast_defs::ParamKind;
@@ -42,6 +48,10 @@ use oxidized::pos::Pos;
 use oxidized::{aast, ast_defs};
 
 use super::argument_analyzer::{self, get_removed_taints_in_comments};
+use super::format_string_analyzer;
+use super::function_call_return_type_fetcher::{
+    get_array_splice_types, get_preg_match_all_matches_type, get_sorted_vec_type,
+};
 use super::method_call_info::MethodCallInfo;
 
 pub(crate) fn check_arguments_match(
@@ -62,6 +72,23 @@ pub(crate) fn check_arguments_match(
     let functionlike_params = &functionlike_info.params;
     // todo handle map and filter
 
+    if unpacked_arg.is_none()
+        && matches!(
+            functionlike_id,
+            FunctionLikeIdentifier::Function(_) | FunctionLikeIdentifier::Method(_, _)
+        )
+    {
+        check_argument_count(
+            statements_analyzer,
+            args.len(),
+            functionlike_id,
+            functionlike_params,
+            analysis_data,
+            context,
+            function_call_pos,
+        );
+    }
+
     if !type_args.is_empty() {
         for (i, type_arg) in type_args.iter().enumerate() {
             let mut param_type = get_type_from_hint(
@@ -210,8 +237,13 @@ pub(crate) fn check_arguments_match(
 
     let mut reordered_args = args.iter().enumerate().collect::<Vec<_>>();
 
+    // closures are analyzed last, so their parameter types can be inferred from
+    // template types bound by the other arguments
     reordered_args.sort_by(|a, b| {
-        matches!(a.1 .1 .2, aast::Expr_::Lfun(..)).cmp(&matches!(b.1 .1 .2, aast::Expr_::Lfun(..)))
+        matches!(a.1 .1 .2, aast::Expr_::Lfun(..) | aast::Expr_::Efun(..)).cmp(&matches!(
+            b.1 .1 .2,
+            aast::Expr_::Lfun(..) | aast::Expr_::Efun(..)
+        ))
     });
 
     for (argument_offset, (_, arg_expr)) in reordered_args.clone() {
@@ -527,6 +559,17 @@ pub(crate) fn check_arguments_match(
         }
     }
 
+    format_string_analyzer::check_format_arguments(
+        statements_analyzer,
+        functionlike_id,
+        functionlike_info,
+        args,
+        unpacked_arg,
+        analysis_data,
+        context,
+        function_call_pos,
+    );
+
     Ok(())
 }
 
@@ -557,6 +600,7 @@ fn adjust_param_type(
             argument_offset,
             context,
             template_result,
+            statements_analyzer.get_config().widen_loop_isset_mixed,
         );
     }
     if !template_result.template_types.is_empty() {
@@ -592,6 +636,7 @@ fn adjust_param_type(
                 false,
                 None,
                 1,
+                statements_analyzer.get_config().widen_loop_isset_mixed,
             );
         }
 
@@ -743,6 +788,7 @@ fn handle_closure_arg(
         false,
         None,
         1,
+        statements_analyzer.get_config().widen_loop_isset_mixed,
     );
 
     replaced_type =
@@ -760,6 +806,15 @@ fn handle_closure_arg(
         }
     };
 
+    // with ARRAY_FILTER_USE_KEY or ARRAY_FILTER_USE_BOTH the callback receives each key
+    // (and value), not just the value that array_filter's signature describes
+    let array_filter_callback_params =
+        if let FunctionLikeIdentifier::Function(StrId::ARRAY_FILTER) = functionlike_id {
+            get_array_filter_callback_params(args, analysis_data, codebase)
+        } else {
+            None
+        };
+
     for (param_offset, param_storage) in closure_storage.params.iter_mut().enumerate() {
         if param_storage.signature_type.is_none() {
             let mut newly_inferred_type = None;
@@ -789,6 +844,22 @@ fn handle_closure_arg(
             if let Some(newly_inferred_type) = newly_inferred_type {
                 param_storage.signature_type = Some(newly_inferred_type);
             }
+
+            if let Some(array_filter_callback_params) = &array_filter_callback_params {
+                param_storage.signature_type =
+                    array_filter_callback_params.get(param_offset).cloned();
+            }
+        } else if array_filter_callback_params.is_none() {
+            check_closure_param_compatibility(
+                statements_analyzer,
+                analysis_data,
+                context,
+                functionlike_id,
+                &replaced_type,
+                closure_expr.pos(),
+                param_offset,
+                param_storage,
+            );
         }
 
         if matches!(
@@ -816,10 +887,15 @@ fn handle_closure_arg(
                 | StrId::LIB_KEYSET_MAP_WITH_KEY
                 | StrId::LIB_DICT_MAP_WITH_KEY_ASYNC
                 | StrId::LIB_DICT_FROM_KEYS
-                | StrId::LIB_DICT_FROM_KEYS_ASYNC,
+                | StrId::LIB_DICT_FROM_KEYS_ASYNC
+                | StrId::ARRAY_FILTER,
             ) = functionlike_id
             {
-                if param_offset == 0 {
+                if param_offset == 0
+                    || array_filter_callback_params
+                        .as_ref()
+                        .map_or(false, |params| param_offset < params.len())
+                {
                     if let Some(ref mut signature_type) = param_storage.signature_type {
                         add_array_fetch_dataflow(
                             statements_analyzer,
@@ -840,6 +916,129 @@ fn handle_closure_arg(
         .insert(closure_expr.pos().clone(), closure_storage);
 }
 
+// Reports explicitly-typed closure params that can't accept the values the called
+// function will pass them, e.g. an int param for a closure mapping over a vec<string>
+fn check_closure_param_compatibility(
+    statements_analyzer: &StatementsAnalyzer,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+    functionlike_id: &FunctionLikeIdentifier,
+    replaced_type: &TUnion,
+    closure_pos: &Pos,
+    param_offset: usize,
+    param_storage: &FunctionLikeParameter,
+) {
+    let declared_param_type = if let Some(signature_type) = &param_storage.signature_type {
+        signature_type
+    } else {
+        return;
+    };
+
+    let codebase = statements_analyzer.get_codebase();
+    let interner = statements_analyzer.get_interner();
+
+    for replaced_type_part in &replaced_type.types {
+        if let TAtomic::TClosure {
+            params: replaced_params,
+            ..
+        } = replaced_type_part
+        {
+            let expected_param_type = if let Some(FnParameter {
+                signature_type: Some(signature_type),
+                ..
+            }) = replaced_params.get(param_offset)
+            {
+                signature_type
+            } else {
+                continue;
+            };
+
+            // unbound templates and mixed tell us nothing about what will be passed
+            if expected_param_type.is_mixed() || expected_param_type.has_template_types() {
+                continue;
+            }
+
+            if !union_type_comparator::is_contained_by(
+                codebase,
+                expected_param_type,
+                declared_param_type,
+                false,
+                false,
+                false,
+                &mut TypeComparisonResult::new(),
+            ) {
+                analysis_data.maybe_add_issue(
+                    Issue::new(
+                        IssueKind::IncompatibleClosureParameter,
+                        format!(
+                            "Closure parameter {} has type {}, but {} passes it {}",
+                            interner.lookup(&param_storage.name.0),
+                            declared_param_type.get_id(Some(interner)),
+                            functionlike_id.to_string(interner),
+                            expected_param_type.get_id(Some(interner)),
+                        ),
+                        statements_analyzer.get_hpos(closure_pos),
+                        &context.function_context.calling_functionlike_id,
+                    ),
+                    statements_analyzer.get_config(),
+                    statements_analyzer.get_file_path_actual(),
+                );
+
+                return;
+            }
+        }
+    }
+}
+
+fn get_array_filter_callback_params(
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    analysis_data: &FunctionAnalysisData,
+    codebase: &CodebaseInfo,
+) -> Option<Vec<TUnion>> {
+    let (_, mode_expr) = args.get(2)?;
+
+    // the mode arg hasn't been analyzed yet, so we look at the expression itself
+    let use_key_only = match &mode_expr.2 {
+        aast::Expr_::Id(id) => match id.1.trim_start_matches('\\') {
+            "ARRAY_FILTER_USE_KEY" => true,
+            "ARRAY_FILTER_USE_BOTH" => false,
+            _ => return None,
+        },
+        aast::Expr_::Int(value) => match value.as_str() {
+            "2" => true,
+            "1" => false,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let input_type = analysis_data.get_expr_type(args[0].1.pos())?;
+
+    let mut key_type = None;
+    let mut value_type = None;
+
+    for input_atomic in &input_type.types {
+        let (atomic_key_type, atomic_value_type) = get_arrayish_params(input_atomic, codebase)?;
+
+        key_type = Some(combine_optional_union_types(
+            key_type.as_ref(),
+            Some(&atomic_key_type),
+            codebase,
+        ));
+        value_type = Some(combine_optional_union_types(
+            value_type.as_ref(),
+            Some(&atomic_value_type),
+            codebase,
+        ));
+    }
+
+    if use_key_only {
+        Some(vec![key_type?])
+    } else {
+        Some(vec![value_type?, key_type?])
+    }
+}
+
 fn map_class_generic_params(
     class_generic_params: &IndexMap<StrId, Vec<(GenericParent, Arc<TUnion>)>>,
     param_type: &mut TUnion,
@@ -849,6 +1048,7 @@ fn map_class_generic_params(
     argument_offset: usize,
     context: &mut BlockContext,
     template_result: &mut TemplateResult,
+    widen_loop_isset_mixed: bool,
 ) {
     let arg_has_template_types = arg_value_type.has_template_types();
 
@@ -882,6 +1082,7 @@ fn map_class_generic_params(
         false,
         None,
         1,
+        widen_loop_isset_mixed,
     );
 
     if arg_has_template_types {
@@ -898,6 +1099,7 @@ fn map_class_generic_params(
             false,
             None,
             1,
+            widen_loop_isset_mixed,
         );
     }
 }
@@ -1015,6 +1217,7 @@ fn handle_possibly_matching_inout_param(
             false,
             None,
             1,
+            statements_analyzer.get_config().widen_loop_isset_mixed,
         );
 
         if !template_result.lower_bounds.is_empty() {
@@ -1055,6 +1258,78 @@ fn handle_possibly_matching_inout_param(
         &mut analysis_data.data_flow_graph,
     );
 
+    // settype($foo, 'int') rebinds $foo to the requested type
+    if matches!(
+        functionlike_id,
+        FunctionLikeIdentifier::Function(StrId::SETTYPE)
+    ) && argument_offset == 0
+    {
+        if let Some((_, aast::Expr(_, _, aast::Expr_::String(type_name)))) = all_args.get(1) {
+            if let Some(settype_type) = get_settype_type(&type_name.to_string()) {
+                inout_type = settype_type;
+            }
+        }
+    }
+
+    // usort($foo, ...) only reorders the elements of a vec
+    if matches!(
+        functionlike_id,
+        FunctionLikeIdentifier::Function(StrId::USORT)
+    ) && argument_offset == 0
+    {
+        if let Some(arg_type) = &arg_type {
+            if arg_type
+                .types
+                .iter()
+                .all(|atomic| matches!(atomic, TAtomic::TVec { .. }))
+            {
+                if let Some(sorted_type) = get_sorted_vec_type(arg_type, codebase) {
+                    inout_type = sorted_type;
+                }
+            }
+        }
+    }
+
+    // array_walk($foo, (inout $v) ==> ...) retypes every element of $foo
+    if matches!(
+        functionlike_id,
+        FunctionLikeIdentifier::Function(StrId::ARRAY_WALK)
+    ) && argument_offset == 0
+    {
+        if let (Some(arg_type), Some((_, callback_arg))) = (&arg_type, all_args.get(1)) {
+            if let Some(callback_type) = analysis_data.get_expr_type(callback_arg.pos()) {
+                if let Some(walked_type) = get_array_walk_type(arg_type, callback_type) {
+                    inout_type = walked_type;
+                }
+            }
+        }
+    }
+
+    // array_splice($foo, ...) removes a slice of $foo and splices in the replacement
+    if matches!(
+        functionlike_id,
+        FunctionLikeIdentifier::Function(StrId::ARRAY_SPLICE)
+    ) && argument_offset == 0
+    {
+        if let Some((_, spliced_type)) = get_array_splice_types(all_args, analysis_data, codebase) {
+            inout_type = spliced_type;
+        }
+    }
+
+    // preg_match_all_with_matches(...) fills $matches according to the pattern's capture groups.
+    // Taint analysis keeps the declared type, so reading a group the pattern doesn't define
+    // still carries taint instead of giving nothing
+    if matches!(
+        functionlike_id,
+        FunctionLikeIdentifier::Function(StrId::PREG_MATCH_ALL_WITH_MATCHES)
+    ) && argument_offset == 2
+        && analysis_data.data_flow_graph.kind == GraphKind::FunctionBody
+    {
+        if let Some(matches_type) = get_preg_match_all_matches_type(all_args, analysis_data) {
+            inout_type = matches_type;
+        }
+    }
+
     let arg_type = arg_type.unwrap_or(get_mixed_any());
 
     let assignment_node = DataFlowNode::get_for_method_argument_out(
@@ -1165,6 +1440,94 @@ fn handle_possibly_matching_inout_param(
     Ok(())
 }
 
+fn get_array_walk_type(collection_type: &TUnion, callback_type: &TUnion) -> Option<TUnion> {
+    if !callback_type.is_single() {
+        return None;
+    }
+
+    let new_value_type = if let TAtomic::TClosure { params, .. } = callback_type.get_single() {
+        match params.first() {
+            Some(FnParameter {
+                is_inout: true,
+                signature_type: Some(signature_type),
+                ..
+            }) => (**signature_type).clone(),
+            // a callback that takes the element by value can't change it
+            _ => return Some(collection_type.clone()),
+        }
+    } else {
+        return None;
+    };
+
+    let mut new_types = vec![];
+
+    for atomic in &collection_type.types {
+        new_types.push(match atomic {
+            TAtomic::TVec {
+                known_items,
+                type_param,
+                known_count,
+                non_empty,
+            } => TAtomic::TVec {
+                known_items: known_items.as_ref().map(|known_items| {
+                    known_items
+                        .iter()
+                        .map(|(offset, (possibly_undefined, _))| {
+                            (*offset, (*possibly_undefined, new_value_type.clone()))
+                        })
+                        .collect()
+                }),
+                type_param: if type_param.is_nothing() {
+                    type_param.clone()
+                } else {
+                    Box::new(new_value_type.clone())
+                },
+                known_count: *known_count,
+                non_empty: *non_empty,
+            },
+            TAtomic::TDict {
+                known_items,
+                params,
+                non_empty,
+                ..
+            } => TAtomic::TDict {
+                known_items: known_items.as_ref().map(|known_items| {
+                    known_items
+                        .iter()
+                        .map(|(key, (possibly_undefined, _))| {
+                            (
+                                key.clone(),
+                                (*possibly_undefined, Arc::new(new_value_type.clone())),
+                            )
+                        })
+                        .collect()
+                }),
+                params: params
+                    .as_ref()
+                    .map(|(key_param, _)| (key_param.clone(), Box::new(new_value_type.clone()))),
+                non_empty: *non_empty,
+                shape_name: None,
+            },
+            _ => {
+                return None;
+            }
+        });
+    }
+
+    Some(TUnion::new(new_types))
+}
+
+fn get_settype_type(type_name: &str) -> Option<TUnion> {
+    Some(match type_name {
+        "bool" | "boolean" => get_bool(),
+        "int" | "integer" => get_int(),
+        "float" | "double" => get_float(),
+        "string" => get_string(),
+        "null" => get_null(),
+        _ => return None,
+    })
+}
+
 fn refine_template_result_for_functionlike(
     template_result: &mut TemplateResult,
     codebase: &CodebaseInfo,
@@ -1343,3 +1706,59 @@ pub(crate) fn get_template_types_for_call(
 
     expanded_template_types
 }
+
+fn check_argument_count(
+    statements_analyzer: &StatementsAnalyzer,
+    arg_count: usize,
+    functionlike_id: &FunctionLikeIdentifier,
+    functionlike_params: &[FunctionLikeParameter],
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+    function_call_pos: &Pos,
+) {
+    let required_count = functionlike_params
+        .iter()
+        .filter(|param| !param.is_optional && !param.is_variadic)
+        .count();
+
+    let max_count = if functionlike_params.iter().any(|param| param.is_variadic) {
+        None
+    } else {
+        Some(functionlike_params.len())
+    };
+
+    let (issue_kind, description) = if arg_count < required_count {
+        (
+            IssueKind::TooFewArguments,
+            format!(
+                "Too few arguments for {} - expecting at least {} but saw {}",
+                functionlike_id.to_string(statements_analyzer.get_interner()),
+                required_count,
+                arg_count
+            ),
+        )
+    } else if let Some(max_count) = max_count.filter(|max_count| arg_count > *max_count) {
+        (
+            IssueKind::TooManyArguments,
+            format!(
+                "Too many arguments for {} - expecting at most {} but saw {}",
+                functionlike_id.to_string(statements_analyzer.get_interner()),
+                max_count,
+                arg_count
+            ),
+        )
+    } else {
+        return;
+    };
+
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            issue_kind,
+            description,
+            statements_analyzer.get_hpos(function_call_pos),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
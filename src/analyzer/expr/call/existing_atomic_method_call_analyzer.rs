@@ -13,6 +13,7 @@ use hakana_reflection_info::{GenericParent, VarId, EFFECT_WRITE_LOCAL};
 use hakana_str::StrId;
 use hakana_type::get_null;
 use hakana_type::template::standin_type_replacer;
+use hakana_type::type_expander::{self, TypeExpansionOptions};
 use hakana_type::{
     add_union_type, get_arraykey, get_dict, get_mixed_any, template::TemplateResult,
 };
@@ -198,6 +199,7 @@ pub(crate) fn analyze(
                     false,
                     None,
                     1,
+                    statements_analyzer.get_config().widen_loop_isset_mixed,
                 );
             }
         }
@@ -278,6 +280,30 @@ pub(crate) fn analyze(
     Ok(return_type_candidate)
 }
 
+// Shapes typed via a type constant or a generic are accessed through their bound,
+// which mirrors how regular array access treats them
+fn get_shape_atomics(dict_type: &TUnion) -> Vec<&TAtomic> {
+    let mut shape_atomics = vec![];
+
+    for atomic in &dict_type.types {
+        match atomic {
+            TAtomic::TTypeAlias {
+                as_type: Some(as_type),
+                ..
+            }
+            | TAtomic::TGenericParam { as_type, .. }
+            | TAtomic::TClassTypeConstant { as_type, .. } => {
+                shape_atomics.extend(get_shape_atomics(as_type));
+            }
+            _ => {
+                shape_atomics.push(atomic);
+            }
+        }
+    }
+
+    shape_atomics
+}
+
 fn handle_shapes_static_method(
     method_id: &MethodIdentifier,
     call_expr: (
@@ -419,7 +445,7 @@ fn handle_shapes_static_method(
                     let mut has_matching_dict_key = false;
                     let is_nullable = dict_type.is_nullable();
 
-                    for atomic_type in &dict_type.types {
+                    for atomic_type in get_shape_atomics(&dict_type) {
                         if let TAtomic::TDict { .. } = atomic_type {
                             let mut expr_type_inner = handle_array_access_on_dict(
                                 statements_analyzer,
@@ -545,7 +571,7 @@ fn handle_shapes_static_method(
                 if arg_type.is_mixed() {
                     get_dict(get_arraykey(true), get_mixed_any())
                 } else {
-                    arg_type
+                    get_shape_as_dict_type(statements_analyzer, arg_type, analysis_data)
                 }
             } else {
                 get_mixed_any()
@@ -557,6 +583,33 @@ fn handle_shapes_static_method(
     None
 }
 
+/// The result of `Shapes::toDict` is a plain dict, so any shape alias (including an
+/// opaque one) is expanded to its known items. Open shapes keep their params.
+fn get_shape_as_dict_type(
+    statements_analyzer: &StatementsAnalyzer,
+    mut shape_type: TUnion,
+    analysis_data: &mut FunctionAnalysisData,
+) -> TUnion {
+    type_expander::expand_union(
+        statements_analyzer.get_codebase(),
+        &Some(statements_analyzer.get_interner()),
+        &mut shape_type,
+        &TypeExpansionOptions {
+            expand_all_type_aliases: true,
+            ..Default::default()
+        },
+        &mut analysis_data.data_flow_graph,
+    );
+
+    for atomic in shape_type.types.iter_mut() {
+        if let TAtomic::TDict { shape_name, .. } = atomic {
+            *shape_name = None;
+        }
+    }
+
+    shape_type
+}
+
 fn handle_defined_shape_idx(
     call_expr: (
         &Vec<aast::Targ<()>>,
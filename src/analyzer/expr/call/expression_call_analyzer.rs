@@ -41,6 +41,9 @@ pub(crate) fn analyze(
     )?;
     context.inside_general_use = was_inside_general_use;
 
+    // the callee isn't known statically, so call graph exports can't resolve it
+    analysis_data.has_dynamic_calls = true;
+
     let lhs_type = analysis_data
         .get_rc_expr_type(expr.func.pos())
         .cloned()
@@ -0,0 +1,254 @@
+use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::function_context::FunctionLikeIdentifier;
+use hakana_reflection_info::functionlike_info::FunctionLikeInfo;
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_atomic::TAtomic;
+use hakana_reflection_info::t_union::TUnion;
+use hakana_str::StrId;
+use oxidized::pos::Pos;
+use oxidized::{aast, ast_defs};
+
+use crate::function_analysis_data::FunctionAnalysisData;
+use crate::scope::BlockContext;
+use crate::scope_analyzer::ScopeAnalyzer;
+use crate::statements_analyzer::StatementsAnalyzer;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FormatSpecifier {
+    Int(char),
+    Float(char),
+    Stringish,
+}
+
+impl FormatSpecifier {
+    fn get_expected_type(&self) -> &'static str {
+        match self {
+            FormatSpecifier::Int(_) => "int",
+            FormatSpecifier::Float(_) => "float",
+            FormatSpecifier::Stringish => "Stringish",
+        }
+    }
+
+    fn get_conversion(&self) -> char {
+        match self {
+            FormatSpecifier::Int(c) | FormatSpecifier::Float(c) => *c,
+            FormatSpecifier::Stringish => 's',
+        }
+    }
+
+    fn accepts(&self, atomic: &TAtomic) -> bool {
+        match atomic {
+            TAtomic::TMixed
+            | TAtomic::TMixedFromLoopIsset
+            | TAtomic::TMixedWithFlags(..)
+            | TAtomic::TGenericParam { .. }
+            | TAtomic::TTypeAlias { .. }
+            | TAtomic::TPlaceholder
+            | TAtomic::TNothing => true,
+            TAtomic::TEnum { .. } | TAtomic::TEnumLiteralCase { .. } => true,
+            _ => match self {
+                FormatSpecifier::Int(_) => atomic.is_int(),
+                FormatSpecifier::Float(_) => {
+                    atomic.is_int() || matches!(atomic, TAtomic::TFloat | TAtomic::TNum)
+                }
+                FormatSpecifier::Stringish => {
+                    (atomic.is_some_scalar() && !atomic.is_bool())
+                        || matches!(
+                            atomic,
+                            TAtomic::TNull | TAtomic::TNamedObject { .. } | TAtomic::TObject
+                        )
+                }
+            },
+        }
+    }
+}
+
+pub(crate) fn is_format_string_type(param_type: &TUnion, codebase: &CodebaseInfo) -> bool {
+    param_type.types.iter().any(|atomic| match atomic {
+        TAtomic::TTypeAlias {
+            name: StrId::FORMAT_STRING,
+            ..
+        }
+        | TAtomic::TNamedObject {
+            name: StrId::FORMAT_STRING,
+            ..
+        } => true,
+        // e.g. HH\Lib\Str\SprintfFormatString
+        TAtomic::TTypeAlias { name, .. } => {
+            if let Some(type_definition) = codebase.type_definitions.get(name) {
+                is_format_string_type(&type_definition.actual_type, codebase)
+            } else {
+                false
+            }
+        }
+        _ => false,
+    })
+}
+
+/// Checks the arguments that follow a literal `HH\FormatString<T>` argument against
+/// the conversion specifiers found in that string. Non-literal formats are skipped.
+pub(crate) fn check_format_arguments(
+    statements_analyzer: &StatementsAnalyzer,
+    functionlike_id: &FunctionLikeIdentifier,
+    functionlike_info: &FunctionLikeInfo,
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    unpacked_arg: &Option<aast::Expr<(), ()>>,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+    function_call_pos: &Pos,
+) {
+    let format_offset = if let Some(format_offset) =
+        functionlike_info.params.iter().position(|param| {
+            if let Some(signature_type) = &param.signature_type {
+                is_format_string_type(signature_type, statements_analyzer.get_codebase())
+            } else {
+                false
+            }
+        }) {
+        format_offset
+    } else {
+        return;
+    };
+
+    let format_string = if let Some((_, format_expr)) = args.get(format_offset) {
+        if let Some(format_type) = analysis_data.get_expr_type(format_expr.pos()) {
+            if let Some(format_string) = format_type.get_single_literal_string_value() {
+                format_string
+            } else {
+                return;
+            }
+        } else {
+            return;
+        }
+    } else {
+        return;
+    };
+
+    let specifiers = if let Some(specifiers) = parse_format_specifiers(&format_string) {
+        specifiers
+    } else {
+        return;
+    };
+
+    let interner = statements_analyzer.get_interner();
+    let provided_args = &args[(format_offset + 1)..];
+
+    if unpacked_arg.is_none() && provided_args.len() != specifiers.len() {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::InvalidFormatArgument,
+                format!(
+                    "Format string passed to {} expects {} argument{}, {} provided",
+                    functionlike_id.to_string(interner),
+                    specifiers.len(),
+                    if specifiers.len() == 1 { "" } else { "s" },
+                    provided_args.len(),
+                ),
+                statements_analyzer.get_hpos(function_call_pos),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
+
+    for (i, specifier) in specifiers.iter().enumerate() {
+        let arg_expr = if let Some((_, arg_expr)) = provided_args.get(i) {
+            arg_expr
+        } else {
+            break;
+        };
+
+        let arg_type = if let Some(arg_type) = analysis_data.get_expr_type(arg_expr.pos()) {
+            arg_type
+        } else {
+            continue;
+        };
+
+        if arg_type
+            .types
+            .iter()
+            .all(|atomic| specifier.accepts(atomic))
+        {
+            continue;
+        }
+
+        let arg_type_id = arg_type.get_id(Some(interner));
+
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::InvalidFormatArgument,
+                format!(
+                    "Argument {} of {} expects {} for format specifier %{}, {} provided",
+                    format_offset + i + 2,
+                    functionlike_id.to_string(interner),
+                    specifier.get_expected_type(),
+                    specifier.get_conversion(),
+                    arg_type_id,
+                ),
+                statements_analyzer.get_hpos(arg_expr.pos()),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
+}
+
+/// Returns None when the format contains something we don't model (e.g. positional
+/// arguments or unknown conversions), in which case no checking is done.
+fn parse_format_specifiers(format_string: &str) -> Option<Vec<FormatSpecifier>> {
+    let mut specifiers = vec![];
+    let mut chars = format_string.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            continue;
+        }
+
+        // flags
+        while let Some(&flag) = chars.peek() {
+            match flag {
+                '-' | '+' | ' ' | '0' => {
+                    chars.next();
+                }
+                '\'' => {
+                    chars.next();
+                    chars.next()?;
+                }
+                _ => break,
+            }
+        }
+
+        // width
+        while chars.peek().map_or(false, |c| c.is_ascii_digit()) {
+            chars.next();
+        }
+
+        if chars.peek() == Some(&'$') {
+            return None;
+        }
+
+        // precision
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            while chars.peek().map_or(false, |c| c.is_ascii_digit()) {
+                chars.next();
+            }
+        }
+
+        specifiers.push(match chars.next()? {
+            c @ ('b' | 'c' | 'd' | 'i' | 'o' | 'u' | 'x' | 'X') => FormatSpecifier::Int(c),
+            c @ ('e' | 'E' | 'f' | 'F' | 'g' | 'G') => FormatSpecifier::Float(c),
+            's' => FormatSpecifier::Stringish,
+            _ => return None,
+        });
+    }
+
+    Some(specifiers)
+}
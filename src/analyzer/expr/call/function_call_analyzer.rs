@@ -1,16 +1,19 @@
 use hakana_reflection_info::analysis_result::Replacement;
 use hakana_reflection_info::codebase_info::CodebaseInfo;
-use hakana_reflection_info::t_atomic::DictKey;
+use hakana_reflection_info::t_atomic::{DictKey, TAtomic};
 use hakana_reflection_info::t_union::TUnion;
 use hakana_reflection_info::{VarId, EFFECT_WRITE_LOCAL, EFFECT_WRITE_PROPS};
 use hakana_str::StrId;
+use hakana_type::type_comparator::type_comparison_result::TypeComparisonResult;
 use hakana_type::type_comparator::union_type_comparator;
-use hakana_type::{get_arrayish_params, get_void};
+use hakana_type::type_expander::{self, TypeExpansionOptions};
+use hakana_type::{get_arrayish_params, get_arraykey, get_void};
 use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
 use crate::expr::call::arguments_analyzer;
-use crate::expr::call_analyzer::{apply_effects, check_template_result};
+use crate::expr::call_analyzer::{apply_effects, check_template_result, report_deprecated_usage};
 use crate::expr::{echo_analyzer, exit_analyzer, expression_identifier, isset_analyzer};
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::reconciler;
@@ -19,9 +22,10 @@ use crate::scope_analyzer::ScopeAnalyzer;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt_analyzer::AnalysisError;
 use crate::{expression_analyzer, formula_generator};
-use hakana_reflection_info::assertion::Assertion;
-use hakana_reflection_info::data_flow::graph::GraphKind;
+use hakana_reflection_info::assertion::{Assertion, SubstringPosition};
+use hakana_reflection_info::data_flow::graph::{DataFlowGraph, GraphKind};
 use hakana_reflection_info::functionlike_identifier::FunctionLikeIdentifier;
+use hakana_reflection_info::functionlike_info::FunctionLikeInfo;
 use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::taint::SinkType;
 use hakana_type::template::TemplateResult;
@@ -115,7 +119,10 @@ pub(crate) fn analyze(
 
             // ignore non-existent functions that are in HH\
             // as these can differ between Hakana and hh_server
-            if !interned_name.starts_with("HH\\") && !interned_name.starts_with("xhprof_") {
+            if !interned_name.starts_with("HH\\")
+                && !interned_name.starts_with("xhprof_")
+                && !context.functions_known_to_exist.contains(&name)
+            {
                 analysis_data.maybe_add_issue(
                     Issue::new(
                         IssueKind::NonExistentFunction,
@@ -218,6 +225,21 @@ pub(crate) fn analyze(
         );
     }
 
+    if function_storage.deprecated {
+        report_deprecated_usage(
+            statements_analyzer,
+            analysis_data,
+            context,
+            IssueKind::DeprecatedFunction,
+            format!(
+                "Function {}",
+                statements_analyzer.get_interner().lookup(&name)
+            ),
+            function_storage.deprecation_message,
+            pos,
+        );
+    }
+
     if !function_storage.is_production_code
         && function_storage.user_defined
         && context.function_context.is_production(codebase)
@@ -257,12 +279,284 @@ pub(crate) fn analyze(
         context.has_returned = true;
     }
 
+    if function_storage
+        .params
+        .iter()
+        .any(|param| param.asserted_type.is_some())
+    {
+        apply_param_type_assertions(
+            statements_analyzer,
+            function_storage,
+            expr.2,
+            pos,
+            analysis_data,
+            context,
+        );
+    }
+
     match name {
         StrId::INVARIANT => {
             if let Some((_, first_arg)) = &expr.2.first() {
                 process_invariant(first_arg, context, statements_analyzer, analysis_data);
             }
         }
+        StrId::ARRAY_IS_LIST => {
+            if let Some((_, first_arg)) = &expr.2.first() {
+                let expr_var_id = expression_identifier::get_var_id(
+                    first_arg,
+                    context.function_context.calling_class.as_ref(),
+                    resolved_names,
+                    Some((
+                        statements_analyzer.get_codebase(),
+                        statements_analyzer.get_interner(),
+                    )),
+                );
+
+                if let Some(expr_var_id) = expr_var_id {
+                    analysis_data.if_true_assertions.insert(
+                        (pos.start_offset() as u32, pos.end_offset() as u32),
+                        FxHashMap::from_iter([(expr_var_id, vec![Assertion::IsList])]),
+                    );
+                }
+            }
+        }
+        // the $syntax_only flag doesn't matter here, since we only narrow
+        // to the shapes of value that could be callable
+        StrId::IS_CALLABLE | StrId::IS_CALLABLE_WITH_NAME => {
+            if let Some((_, first_arg)) = &expr.2.first() {
+                let expr_var_id = expression_identifier::get_var_id(
+                    first_arg,
+                    context.function_context.calling_class.as_ref(),
+                    resolved_names,
+                    Some((
+                        statements_analyzer.get_codebase(),
+                        statements_analyzer.get_interner(),
+                    )),
+                );
+
+                if let Some(expr_var_id) = expr_var_id {
+                    analysis_data
+                        .if_true_assertions
+                        .entry((pos.start_offset() as u32, pos.end_offset() as u32))
+                        .or_default()
+                        .insert(expr_var_id, vec![Assertion::IsCallable]);
+                }
+            }
+        }
+        StrId::FUNCTION_EXISTS => {
+            if let Some((_, first_arg)) = &expr.2.first() {
+                if let Some(function_name) = analysis_data
+                    .get_expr_type(first_arg.pos())
+                    .and_then(|arg_type| arg_type.get_single_literal_string_value())
+                {
+                    // if the name was never interned, nothing calls it
+                    if let Some(function_name) = statements_analyzer
+                        .get_interner()
+                        .get(function_name.trim_start_matches('\\'))
+                    {
+                        analysis_data
+                            .if_true_assertions
+                            .entry((pos.start_offset() as u32, pos.end_offset() as u32))
+                            .or_default()
+                            .insert(
+                                "hakana functions".to_string(),
+                                vec![Assertion::FunctionExists(function_name)],
+                            );
+                    }
+                }
+            }
+        }
+        StrId::LIB_STR_CONTAINS
+        | StrId::LIB_STR_STARTS_WITH
+        | StrId::LIB_STR_ENDS_WITH
+        | StrId::STR_CONTAINS
+        | StrId::STR_STARTS_WITH
+        | StrId::STR_ENDS_WITH => {
+            if let (Some((_, haystack_arg)), Some((_, needle_arg))) =
+                (expr.2.first(), expr.2.get(1))
+            {
+                let expr_var_id = expression_identifier::get_var_id(
+                    haystack_arg,
+                    context.function_context.calling_class.as_ref(),
+                    resolved_names,
+                    Some((
+                        statements_analyzer.get_codebase(),
+                        statements_analyzer.get_interner(),
+                    )),
+                );
+
+                let needle = analysis_data
+                    .get_expr_type(needle_arg.pos())
+                    .and_then(|needle_type| needle_type.get_single_literal_string_value());
+
+                if let (Some(expr_var_id), Some(needle)) = (expr_var_id, needle) {
+                    let position = match name {
+                        StrId::LIB_STR_STARTS_WITH | StrId::STR_STARTS_WITH => {
+                            SubstringPosition::Start
+                        }
+                        StrId::LIB_STR_ENDS_WITH | StrId::STR_ENDS_WITH => SubstringPosition::End,
+                        _ => SubstringPosition::Anywhere,
+                    };
+
+                    analysis_data
+                        .if_true_assertions
+                        .entry((pos.start_offset() as u32, pos.end_offset() as u32))
+                        .or_default()
+                        .insert(expr_var_id, vec![Assertion::HasSubstring(position, needle)]);
+                }
+            }
+
+            if name == StrId::LIB_STR_STARTS_WITH && expr.2.len() == 2 {
+                if let GraphKind::WholeProgram(_) = &analysis_data.data_flow_graph.kind {
+                    let expr_var_id = expression_identifier::get_var_id(
+                        &expr.2[0].1,
+                        context.function_context.calling_class.as_ref(),
+                        resolved_names,
+                        Some((
+                            statements_analyzer.get_codebase(),
+                            statements_analyzer.get_interner(),
+                        )),
+                    );
+
+                    let second_arg_type = analysis_data.get_expr_type(expr.2[1].1.pos());
+
+                    // if we have a HH\Lib\Str\starts_with($foo, "/something") check
+                    // we can remove url-specific taints
+                    if let (Some(expr_var_id), Some(second_arg_type)) =
+                        (expr_var_id, second_arg_type)
+                    {
+                        if let Some(str) = second_arg_type.get_single_literal_string_value() {
+                            if str.len() > 1 && str != "http://" && str != "https://" {
+                                if let Some(id) =
+                                    statements_analyzer.get_interner().get(&expr_var_id)
+                                {
+                                    analysis_data
+                                        .if_true_assertions
+                                        .entry((pos.start_offset() as u32, pos.end_offset() as u32))
+                                        .or_default()
+                                        .insert(
+                                            "hakana taints".to_string(),
+                                            vec![Assertion::RemoveTaints(
+                                                VarId(id),
+                                                vec![
+                                                    SinkType::HtmlAttributeUri,
+                                                    SinkType::CurlUri,
+                                                    SinkType::RedirectUri,
+                                                ],
+                                            )],
+                                        );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        StrId::LIB_KEYSET_MAP | StrId::LIB_KEYSET_MAP_WITH_KEY | StrId::LIB_KEYSET_MAP_ASYNC => {
+            if let Some((_, callable_arg)) = expr.2.get(1) {
+                if let Some(mut mapped_type) =
+                    function_call_return_type_fetcher::get_callable_return_type(
+                        statements_analyzer,
+                        callable_arg,
+                        analysis_data,
+                    )
+                {
+                    if name == StrId::LIB_KEYSET_MAP_ASYNC && mapped_type.is_single() {
+                        if let TAtomic::TAwaitable { value } = mapped_type.get_single() {
+                            mapped_type = (**value).clone();
+                        }
+                    }
+
+                    if !mapped_type.is_mixed()
+                        && !union_type_comparator::is_contained_by(
+                            codebase,
+                            &mapped_type,
+                            &get_arraykey(false),
+                            false,
+                            false,
+                            false,
+                            &mut TypeComparisonResult::new(),
+                        )
+                    {
+                        analysis_data.maybe_add_issue(
+                            Issue::new(
+                                IssueKind::InvalidArgument,
+                                format!(
+                                    "Argument 2 of {} must return an arraykey, {} returned",
+                                    statements_analyzer.get_interner().lookup(&name),
+                                    mapped_type.get_id(Some(statements_analyzer.get_interner()))
+                                ),
+                                statements_analyzer.get_hpos(callable_arg.pos()),
+                                &context.function_context.calling_functionlike_id,
+                            ),
+                            statements_analyzer.get_config(),
+                            statements_analyzer.get_file_path_actual(),
+                        );
+                    }
+                }
+            }
+        }
+        StrId::USORT | StrId::UASORT | StrId::UKSORT => {
+            if let Some((_, comparator_arg)) = expr.2.get(1) {
+                if let Some(comparator_return_type) =
+                    function_call_return_type_fetcher::get_callable_return_type(
+                        statements_analyzer,
+                        comparator_arg,
+                        analysis_data,
+                    )
+                {
+                    // a bool result only says whether the first item is bigger, which
+                    // doesn't give a consistent ordering
+                    if comparator_return_type
+                        .types
+                        .iter()
+                        .any(|atomic| atomic.is_bool())
+                    {
+                        analysis_data.maybe_add_issue(
+                            Issue::new(
+                                IssueKind::InvalidComparator,
+                                format!(
+                                    "Comparator passed to {} returns {}, but should return an int",
+                                    statements_analyzer.get_interner().lookup(&name),
+                                    comparator_return_type
+                                        .get_id(Some(statements_analyzer.get_interner()))
+                                ),
+                                statements_analyzer.get_hpos(comparator_arg.pos()),
+                                &context.function_context.calling_functionlike_id,
+                            ),
+                            statements_analyzer.get_config(),
+                            statements_analyzer.get_file_path_actual(),
+                        );
+                    }
+                }
+            }
+        }
+        StrId::RANGE | StrId::LIB_VEC_RANGE => {
+            if let Some((_, step_arg)) = expr.2.get(2) {
+                if let Some(step) = analysis_data
+                    .get_expr_type(step_arg.pos())
+                    .and_then(|step_type| step_type.get_single_literal_int_value())
+                {
+                    // range() accepts negative steps, Vec\range throws on them
+                    if step == 0 || (step < 0 && name == StrId::LIB_VEC_RANGE) {
+                        analysis_data.maybe_add_issue(
+                            Issue::new(
+                                IssueKind::InvalidArgument,
+                                format!(
+                                    "Argument 3 of {} must be a positive step, {} provided",
+                                    statements_analyzer.get_interner().lookup(&name),
+                                    step
+                                ),
+                                statements_analyzer.get_hpos(step_arg.pos()),
+                                &context.function_context.calling_functionlike_id,
+                            ),
+                            statements_analyzer.get_config(),
+                            statements_analyzer.get_file_path_actual(),
+                        );
+                    }
+                }
+            }
+        }
         StrId::LIB_C_CONTAINS
         | StrId::LIB_C_CONTAINS_KEY
         | StrId::LIB_DICT_CONTAINS
@@ -379,52 +673,6 @@ pub(crate) fn analyze(
                 }
             }
         }
-        StrId::LIB_STR_STARTS_WITH => {
-            if expr.2.len() == 2 {
-                if let GraphKind::WholeProgram(_) = &analysis_data.data_flow_graph.kind {
-                    let expr_var_id = expression_identifier::get_var_id(
-                        &expr.2[0].1,
-                        context.function_context.calling_class.as_ref(),
-                        resolved_names,
-                        Some((
-                            statements_analyzer.get_codebase(),
-                            statements_analyzer.get_interner(),
-                        )),
-                    );
-
-                    let second_arg_type = analysis_data.get_expr_type(expr.2[1].1.pos());
-
-                    // if we have a HH\Lib\Str\starts_with($foo, "/something") check
-                    // we can remove url-specific taints
-                    if let (Some(expr_var_id), Some(second_arg_type)) =
-                        (expr_var_id, second_arg_type)
-                    {
-                        if let Some(str) = second_arg_type.get_single_literal_string_value() {
-                            if str.len() > 1 && str != "http://" && str != "https://" {
-                                if let Some(id) =
-                                    statements_analyzer.get_interner().get(&expr_var_id)
-                                {
-                                    analysis_data.if_true_assertions.insert(
-                                        (pos.start_offset() as u32, pos.end_offset() as u32),
-                                        FxHashMap::from_iter([(
-                                            "hakana taints".to_string(),
-                                            vec![Assertion::RemoveTaints(
-                                                VarId(id),
-                                                vec![
-                                                    SinkType::HtmlAttributeUri,
-                                                    SinkType::CurlUri,
-                                                    SinkType::RedirectUri,
-                                                ],
-                                            )],
-                                        )]),
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
         StrId::LIB_REGEX_MATCHES => {
             if expr.2.len() == 2 {
                 if let GraphKind::WholeProgram(_) = &analysis_data.data_flow_graph.kind {
@@ -596,6 +844,87 @@ fn process_invariant(
     }
 }
 
+// Narrows arguments passed to parameters marked with <<Hakana\AssertsType('...')>>,
+// since the function only returns when the argument has that type
+fn apply_param_type_assertions(
+    statements_analyzer: &StatementsAnalyzer,
+    function_storage: &FunctionLikeInfo,
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut BlockContext,
+) {
+    let mut assertions = BTreeMap::new();
+
+    for (param, (_, arg_expr)) in function_storage.params.iter().zip(args) {
+        let mut asserted_type = if let Some(asserted_type) = &param.asserted_type {
+            asserted_type.clone()
+        } else {
+            continue;
+        };
+
+        let arg_var_id = expression_identifier::get_var_id(
+            arg_expr,
+            context.function_context.calling_class.as_ref(),
+            statements_analyzer.get_file_analyzer().resolved_names,
+            Some((
+                statements_analyzer.get_codebase(),
+                statements_analyzer.get_interner(),
+            )),
+        );
+
+        if let Some(arg_var_id) = arg_var_id {
+            type_expander::expand_union(
+                statements_analyzer.get_codebase(),
+                &Some(statements_analyzer.get_interner()),
+                &mut asserted_type,
+                &TypeExpansionOptions::default(),
+                &mut DataFlowGraph::new(GraphKind::FunctionBody),
+            );
+
+            assertions.insert(
+                arg_var_id,
+                vec![asserted_type
+                    .types
+                    .into_iter()
+                    .map(Assertion::IsType)
+                    .collect::<Vec<_>>()],
+            );
+        }
+    }
+
+    if assertions.is_empty() {
+        return;
+    }
+
+    let mut changed_var_ids = FxHashSet::default();
+
+    reconciler::reconcile_keyed_types(
+        &assertions,
+        BTreeMap::new(),
+        context,
+        &mut changed_var_ids,
+        &assertions.keys().cloned().collect(),
+        statements_analyzer,
+        analysis_data,
+        pos,
+        true,
+        false,
+        &FxHashMap::default(),
+    );
+
+    if !changed_var_ids.is_empty() {
+        context.clauses = BlockContext::remove_reconciled_clauses(
+            &context.clauses.iter().map(|v| (**v).clone()).collect(),
+            &changed_var_ids,
+        )
+        .0
+        .into_iter()
+        .map(Rc::new)
+        .collect();
+    }
+}
+
 fn check_array_key_or_value_type(
     codebase: &CodebaseInfo,
     statements_analyzer: &StatementsAnalyzer,
@@ -7,20 +7,23 @@ use hakana_reflection_info::data_flow::node::{DataFlowNode, DataFlowNodeKind};
 use hakana_reflection_info::data_flow::path::{ArrayDataKind, PathKind};
 use hakana_reflection_info::function_context::FunctionLikeIdentifier;
 use hakana_reflection_info::functionlike_info::FunctionLikeInfo;
+use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::{DictKey, TAtomic};
 use hakana_reflection_info::t_union::TUnion;
 use hakana_reflection_info::taint::SinkType;
 use hakana_reflection_info::{GenericParent, EFFECT_IMPURE};
+use hakana_reflector::simple_type_inferer::int_from_string;
 use hakana_str::{Interner, StrId};
 use hakana_type::type_comparator::type_comparison_result::TypeComparisonResult;
 use hakana_type::type_comparator::union_type_comparator;
 use hakana_type::type_expander::TypeExpansionOptions;
 use hakana_type::{
-    add_union_type, extend_dataflow_uniquely, get_arrayish_params, get_float, get_int,
-    get_literal_string, get_mixed, get_mixed_any, get_mixed_vec, get_nothing, get_null, get_object,
-    get_string, get_vec, template, type_expander, wrap_atomic,
+    add_optional_union_type, add_union_type, extend_dataflow_uniquely, get_arrayish_params,
+    get_arraykey, get_dict, get_false, get_float, get_gettype_names, get_int, get_keyset,
+    get_literal_int, get_literal_string, get_mixed, get_mixed_any, get_mixed_vec, get_nothing,
+    get_null, get_object, get_string, get_true, get_vec, template, type_expander, wrap_atomic,
 };
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
@@ -60,6 +63,7 @@ pub(crate) fn fetch(
             statements_analyzer,
             name,
             expr.2,
+            expr.3,
             pos,
             codebase,
             analysis_data,
@@ -87,6 +91,18 @@ pub(crate) fn fetch(
 
             for (template_name, _) in &function_storage.template_types {
                 if template_result.lower_bounds.get(template_name).is_none() {
+                    if statements_analyzer.get_config().report_unresolved_templates {
+                        report_unresolved_template(
+                            statements_analyzer,
+                            template_name,
+                            fn_id,
+                            function_return_type,
+                            pos,
+                            analysis_data,
+                            context,
+                        );
+                    }
+
                     template_result.lower_bounds.insert(
                         *template_name,
                         FxHashMap::from_iter([(
@@ -161,10 +177,53 @@ pub(crate) fn fetch(
     )
 }
 
+// Templates that aren't used in the return type can't make the result collapse
+// to nothing, so they're never reported
+fn report_unresolved_template(
+    statements_analyzer: &StatementsAnalyzer,
+    template_name: &StrId,
+    fn_id: &StrId,
+    function_return_type: &TUnion,
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+) {
+    let used_in_return_type = function_return_type.get_template_types().iter().any(|t| {
+        matches!(
+            t,
+            TAtomic::TGenericParam { param_name, .. }
+                | TAtomic::TGenericClassname { param_name, .. }
+                | TAtomic::TGenericTypename { param_name, .. }
+                if param_name == template_name
+        )
+    });
+
+    if !used_in_return_type {
+        return;
+    }
+
+    let interner = statements_analyzer.get_interner();
+
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            IssueKind::UnresolvedTemplate,
+            format!(
+                "Could not infer template {} of {} from the call's arguments, so it is typed as nothing",
+                interner.lookup(template_name),
+                interner.lookup(fn_id),
+            ),
+            statements_analyzer.get_hpos(pos),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
 fn handle_special_functions(
     statements_analyzer: &StatementsAnalyzer,
     name: &StrId,
     args: &Vec<(ast_defs::ParamKind, aast::Expr<(), ()>)>,
+    unpacked_arg: &Option<aast::Expr<(), ()>>,
     pos: &Pos,
     codebase: &CodebaseInfo,
     analysis_data: &mut FunctionAnalysisData,
@@ -195,12 +254,25 @@ fn handle_special_functions(
         &StrId::GLOBAL_GET => {
             if let Some((_, arg_expr)) = args.first() {
                 if let Some(expr_type) = analysis_data.get_expr_type(arg_expr.pos()) {
+                    let in_entry_point =
+                        if let Some(FunctionLikeIdentifier::Function(function_name)) =
+                            &context.function_context.calling_functionlike_id
+                        {
+                            codebase
+                                .functionlike_infos
+                                .get(&(*function_name, StrId::EMPTY))
+                                .map_or(false, |functionlike_info| functionlike_info.is_entry_point)
+                        } else {
+                            false
+                        };
+
                     expr_type.get_single_literal_string_value().map(|value| {
                         variable_fetch_analyzer::get_type_for_superglobal(
                             statements_analyzer,
                             value,
                             pos,
                             analysis_data,
+                            in_entry_point,
                         )
                     })
                 } else {
@@ -470,10 +542,58 @@ fn handle_special_functions(
 
             None
         }
-        &StrId::LIB_STR_TRIM
-        | &StrId::LIB_STR_STRIP_SUFFIX
-        | &StrId::LIB_STR_SLICE
-        | &StrId::LIB_STR_REPLACE => {
+        &StrId::PRINTF | &StrId::VPRINTF => {
+            // both return the number of bytes written, which we know exactly when
+            // printf's format and arguments are all literals
+            if name == &StrId::PRINTF {
+                if let Some(output_length) = get_printf_output_length(args, analysis_data) {
+                    return Some(get_literal_int(output_length as i64));
+                }
+            }
+
+            Some(get_int())
+        }
+        &StrId::LIB_STR_STRIP_PREFIX | &StrId::LIB_STR_STRIP_SUFFIX => {
+            let string_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+            let affix_type = analysis_data.get_expr_type(args.get(1)?.1.pos())?;
+
+            // literal subjects (e.g. narrowed by a matching Str\starts_with check) fold exactly
+            if let Some(affix) = affix_type.get_single_literal_string_value() {
+                let mut stripped_type = None;
+
+                for atomic in &string_type.types {
+                    if let TAtomic::TLiteralString { value } = atomic {
+                        let stripped_value = if name == &StrId::LIB_STR_STRIP_PREFIX {
+                            value.strip_prefix(affix.as_str())
+                        } else {
+                            value.strip_suffix(affix.as_str())
+                        };
+
+                        stripped_type = Some(add_optional_union_type(
+                            get_literal_string(stripped_value.unwrap_or(value).to_string()),
+                            stripped_type.as_ref(),
+                            codebase,
+                        ));
+                    } else {
+                        stripped_type = None;
+                        break;
+                    }
+                }
+
+                if stripped_type.is_some() {
+                    return stripped_type;
+                }
+            }
+
+            Some(wrap_atomic(
+                if string_type.all_literals() && affix_type.all_literals() {
+                    TAtomic::TStringWithFlags(false, false, true)
+                } else {
+                    TAtomic::TString
+                },
+            ))
+        }
+        &StrId::LIB_STR_TRIM | &StrId::LIB_STR_SLICE | &StrId::LIB_STR_REPLACE => {
             let mut all_literals = true;
             for (_, arg_expr) in args {
                 if let Some(arg_expr_type) = analysis_data.get_expr_type(arg_expr.pos()) {
@@ -493,6 +613,276 @@ fn handle_special_functions(
                 TAtomic::TString
             }))
         }
+        &StrId::LIB_STR_PAD_LEFT | &StrId::LIB_STR_PAD_RIGHT => {
+            let total_length = analysis_data
+                .get_expr_type(args.get(1)?.1.pos())?
+                .get_single_literal_int_value()?;
+
+            if total_length < 1 {
+                return None;
+            }
+
+            // the result is at least $total_length characters long, and only
+            // single-character strings can be falsy
+            Some(wrap_atomic(if total_length > 1 {
+                TAtomic::TStringWithFlags(true, false, false)
+            } else {
+                TAtomic::TStringWithFlags(false, true, false)
+            }))
+        }
+        &StrId::STR_REPEAT | &StrId::LIB_STR_REPEAT => {
+            let multiplier = analysis_data
+                .get_expr_type(args.get(1)?.1.pos())?
+                .get_single_literal_int_value()?;
+
+            if multiplier == 0 {
+                return Some(get_literal_string("".to_string()));
+            }
+
+            if multiplier < 0 {
+                return None;
+            }
+
+            let string_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            if multiplier == 1 {
+                return Some(TUnion::new(string_type.types.clone()));
+            }
+
+            if !string_type.types.iter().all(|atomic| match atomic {
+                TAtomic::TLiteralString { value } => !value.is_empty(),
+                TAtomic::TStringWithFlags(_, is_non_empty, _) => *is_non_empty,
+                _ => atomic.is_truthy(),
+            }) {
+                return None;
+            }
+
+            // repeating a non-empty string at least twice can never give "0"
+            Some(wrap_atomic(TAtomic::TStringWithFlags(true, false, false)))
+        }
+        &StrId::ARRAY_SUM | &StrId::ARRAY_PRODUCT => {
+            let container_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let int_values = if container_type.is_single() {
+                get_defined_vec_items(container_type.get_single()).and_then(|items| {
+                    items
+                        .iter()
+                        .map(|item| item.get_single_literal_int_value())
+                        .collect::<Option<Vec<_>>>()
+                })
+            } else {
+                None
+            };
+
+            if let Some(int_values) = int_values {
+                // overflowing ints become floats at runtime
+                return Some(if name == &StrId::ARRAY_SUM {
+                    int_values
+                        .into_iter()
+                        .try_fold(0i64, |carry, value| carry.checked_add(value))
+                        .map_or(get_float(), get_literal_int)
+                } else {
+                    int_values
+                        .into_iter()
+                        .try_fold(1i64, |carry, value| carry.checked_mul(value))
+                        .map_or(get_float(), get_literal_int)
+                });
+            }
+
+            let mut has_int = false;
+            let mut has_float = false;
+            let mut possibly_empty = false;
+
+            for atomic in &container_type.types {
+                let (_, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                if !atomic.is_non_empty_vec() && !atomic.is_non_empty_dict() {
+                    possibly_empty = true;
+                }
+
+                for value_atomic in &value_param.types {
+                    match value_atomic {
+                        TAtomic::TFloat => {
+                            has_float = true;
+                        }
+                        TAtomic::TInt | TAtomic::TLiteralInt { .. } => {
+                            has_int = true;
+                        }
+                        // anything else is coerced, so we can't say much
+                        _ => return None,
+                    }
+                }
+            }
+
+            // an empty array gives int 0 (or 1), even when the elements are floats
+            if !has_float {
+                Some(get_int())
+            } else if !has_int && !possibly_empty {
+                Some(get_float())
+            } else {
+                None
+            }
+        }
+        &StrId::LIB_DICT_TAKE | &StrId::LIB_DICT_DROP => {
+            let dict_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+            let count = analysis_data
+                .get_expr_type(args.get(1)?.1.pos())?
+                .get_single_literal_int_value();
+
+            if !dict_type.is_single() {
+                return None;
+            }
+
+            Some(wrap_atomic(get_dict_window(
+                dict_type.get_single(),
+                count,
+                name == &StrId::LIB_DICT_TAKE,
+            )?))
+        }
+        &StrId::LIB_VEC_ZIP => {
+            let first_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+            let second_type = analysis_data.get_expr_type(args.get(1)?.1.pos())?;
+
+            let first_items = if first_type.is_single() {
+                get_defined_vec_items(first_type.get_single())
+            } else {
+                None
+            };
+
+            let second_items = if second_type.is_single() {
+                get_defined_vec_items(second_type.get_single())
+            } else {
+                None
+            };
+
+            match (first_items, second_items) {
+                // zipping stops at the end of the shorter vec
+                (Some(first_items), Some(second_items)) => Some(get_vec_from_items(
+                    first_items
+                        .into_iter()
+                        .zip(second_items)
+                        .map(|(first_item, second_item)| {
+                            get_vec_from_items(vec![first_item, second_item])
+                        })
+                        .collect(),
+                )),
+                (Some(items), _) | (_, Some(items)) if items.is_empty() => {
+                    Some(get_vec_from_items(vec![]))
+                }
+                _ => None,
+            }
+        }
+        &StrId::IS_FINITE | &StrId::IS_INFINITE | &StrId::IS_NAN | &StrId::LIB_MATH_IS_NAN => {
+            let (_, arg_expr) = args.first()?;
+
+            // ints are always finite, and we don't track float values, so only
+            // float literals written inline can be folded
+            let value = if let aast::Expr_::Float(value) = &arg_expr.2 {
+                value.parse::<f64>().ok()?
+            } else if analysis_data.get_expr_type(arg_expr.pos())?.is_int() {
+                0.0
+            } else {
+                return None;
+            };
+
+            let result = match name {
+                &StrId::IS_FINITE => value.is_finite(),
+                &StrId::IS_INFINITE => value.is_infinite(),
+                _ => value.is_nan(),
+            };
+
+            Some(if result { get_true() } else { get_false() })
+        }
+        &StrId::LIB_DICT_COUNT_VALUES | &StrId::ARRAY_COUNT_VALUES => {
+            let collection_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut new_types = vec![];
+
+            for atomic in &collection_type.types {
+                if let Some(counts) =
+                    get_defined_arrayish_values(atomic).and_then(get_literal_value_counts)
+                {
+                    new_types.push(get_dict_from_known_items(
+                        counts
+                            .into_iter()
+                            .map(|(key, count)| (key, (false, Arc::new(get_literal_int(count)))))
+                            .collect(),
+                        None,
+                    ));
+                } else {
+                    let (_, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                    // values that aren't arraykeys are skipped, with a warning
+                    let key_param = if union_type_comparator::is_contained_by(
+                        codebase,
+                        &value_param,
+                        &get_arraykey(false),
+                        false,
+                        false,
+                        false,
+                        &mut TypeComparisonResult::new(),
+                    ) {
+                        value_param
+                    } else {
+                        get_arraykey(false)
+                    };
+
+                    new_types.push(TAtomic::TDict {
+                        known_items: None,
+                        params: Some((Box::new(key_param), Box::new(get_int()))),
+                        non_empty: false,
+                        shape_name: None,
+                    });
+                }
+            }
+
+            Some(TUnion::new(new_types))
+        }
+        &StrId::LIB_VEC_FILL => {
+            let count = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_int_value()?;
+
+            // negative sizes throw
+            let count = usize::try_from(count).ok()?;
+
+            let value_type = TUnion::new(
+                analysis_data
+                    .get_expr_type(args.get(1)?.1.pos())?
+                    .types
+                    .clone(),
+            );
+
+            let is_literal_value = value_type.is_single()
+                && matches!(
+                    value_type.get_single(),
+                    TAtomic::TLiteralInt { .. }
+                        | TAtomic::TLiteralString { .. }
+                        | TAtomic::TEnumLiteralCase { .. }
+                        | TAtomic::TTrue
+                        | TAtomic::TFalse
+                        | TAtomic::TNull
+                );
+
+            if count == 0 || (is_literal_value && count as u64 <= MAX_RANGE_KNOWN_ITEMS) {
+                return Some(get_vec_from_items(vec![value_type; count]));
+            }
+
+            Some(wrap_atomic(TAtomic::TVec {
+                known_items: None,
+                type_param: Box::new(value_type),
+                known_count: Some(count),
+                non_empty: true,
+            }))
+        }
+        &StrId::LIB_MATH_ABS => {
+            let value = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_int_value()?;
+
+            // abs(PHP_INT_MIN) overflows, so we fall back to the declared type
+            Some(get_literal_int(value.checked_abs()?))
+        }
         &StrId::LIB_STR_SPLIT => {
             let mut all_literals = true;
             for (_, arg_expr) in args {
@@ -513,199 +903,2715 @@ fn handle_special_functions(
                 TAtomic::TString
             })))
         }
-        &StrId::RANGE => {
-            let mut all_ints = true;
-            for (_, arg_expr) in args {
-                if let Some(arg_expr_type) = analysis_data.get_expr_type(arg_expr.pos()) {
-                    if !arg_expr_type.is_int() {
-                        all_ints = false;
-                        break;
-                    }
-                } else {
-                    all_ints = false;
-                    break;
+        &StrId::RANGE | &StrId::LIB_VEC_RANGE => {
+            let mut has_float = false;
+            let mut literal_values = Some(vec![]);
+
+            for (i, (_, arg_expr)) in args.iter().enumerate() {
+                let arg_expr_type = analysis_data.get_expr_type(arg_expr.pos())?;
+
+                // Vec\range takes a nullable step
+                if i == 2 && arg_expr_type.is_null() {
+                    continue;
                 }
+
+                if arg_expr_type.is_float() {
+                    has_float = true;
+                } else if !arg_expr_type.is_int() {
+                    return None;
+                }
+
+                literal_values = literal_values.and_then(|mut values| {
+                    values.push(arg_expr_type.get_single_literal_int_value()?);
+                    Some(values)
+                });
             }
 
-            if all_ints {
-                Some(get_vec(get_int()))
-            } else {
-                None
+            if has_float {
+                return Some(get_vec(get_float()));
+            }
+
+            match literal_values.as_deref() {
+                Some(&[start, end]) => get_range_vec_type(start, end, 1),
+                Some(&[start, end, step]) => get_range_vec_type(
+                    start,
+                    end,
+                    // range() ignores the sign of its step
+                    if name == &StrId::RANGE {
+                        step.checked_abs()?
+                    } else {
+                        step
+                    },
+                ),
+                _ => Some(get_vec(get_int())),
             }
         }
-        &StrId::IDX_FN => {
-            if args.len() >= 2 {
-                let dict_type = analysis_data.get_rc_expr_type(args[0].1.pos()).cloned();
-                let dim_type = analysis_data.get_rc_expr_type(args[1].1.pos()).cloned();
+        &StrId::ARRAY_SPLICE => get_array_splice_types(args, analysis_data, codebase)
+            .map(|(removed_type, _)| removed_type),
+        &StrId::ARRAY_MAP => {
+            if args.len() < 2 {
+                return None;
+            }
 
-                let mut expr_type = None;
+            let mapped_value_type =
+                get_callable_return_type(statements_analyzer, &args[0].1, analysis_data)?;
 
-                if let (Some(dict_type), Some(dim_type)) = (dict_type, dim_type) {
-                    for atomic_type in &dict_type.types {
-                        if let TAtomic::TDict { .. } = atomic_type {
-                            let mut expr_type_inner = handle_array_access_on_dict(
-                                statements_analyzer,
-                                pos,
-                                analysis_data,
-                                context,
-                                atomic_type,
-                                &dim_type,
-                                false,
-                                &mut false,
-                                true,
-                                &mut false,
-                                &mut false,
-                            );
+            // with more than one array, keys are discarded and a list is returned
+            if args.len() > 2 {
+                return Some(get_vec(mapped_value_type));
+            }
 
-                            if args.len() == 2 && !expr_type_inner.is_mixed() {
-                                expr_type_inner =
-                                    add_union_type(expr_type_inner, &get_null(), codebase, false);
-                            }
+            let array_type = analysis_data.get_expr_type(args[1].1.pos())?;
 
-                            expr_type = Some(expr_type_inner);
-                        }
-                    }
+            let literal_mapper = get_single_expression_lambda(&args[0].1);
 
-                    if args.len() > 2 {
-                        let default_type = analysis_data.get_expr_type(args[2].1.pos());
-                        expr_type = expr_type.map(|expr_type| {
-                            if let Some(default_type) = default_type {
-                                add_union_type(expr_type, default_type, codebase, false)
-                            } else {
-                                add_union_type(expr_type, &get_mixed_any(), codebase, false)
-                            }
-                        });
+            // literal items mapped by a simple lambda keep their exact values
+            let get_mapped_item_type = |item_type: &TUnion| {
+                if let (Some((param_name, body_expr)), true) =
+                    (literal_mapper, item_type.is_single())
+                {
+                    if let Some(mapped_atomic) =
+                        evaluate_literal_lambda_body(body_expr, param_name, item_type.get_single())
+                    {
+                        return wrap_atomic(mapped_atomic);
                     }
                 }
 
-                Some(expr_type.unwrap_or(get_mixed_any()))
-            } else {
-                None
-            }
-        }
-        &StrId::DIRNAME => {
-            if args.len() == 1 {
-                let file_type = analysis_data.get_rc_expr_type(args[0].1.pos()).cloned();
+                mapped_value_type.clone()
+            };
 
-                if let Some(file_type) = file_type {
-                    if let Some(literal_value) = file_type.get_single_literal_string_value() {
-                        let path = Path::new(&literal_value);
-                        if let Some(dir) = path.parent() {
-                            return Some(get_literal_string(dir.to_str().unwrap().to_owned()));
+            let mut new_types = vec![];
+
+            for atomic in &array_type.types {
+                new_types.push(match atomic {
+                    TAtomic::TDict {
+                        known_items,
+                        params,
+                        non_empty,
+                        ..
+                    } => TAtomic::TDict {
+                        known_items: known_items.as_ref().map(|known_items| {
+                            known_items
+                                .iter()
+                                .map(|(key, (possibly_undefined, item_type))| {
+                                    (
+                                        key.clone(),
+                                        (
+                                            *possibly_undefined,
+                                            Arc::new(get_mapped_item_type(item_type)),
+                                        ),
+                                    )
+                                })
+                                .collect()
+                        }),
+                        params: params.as_ref().map(|(key_param, _)| {
+                            (key_param.clone(), Box::new(mapped_value_type.clone()))
+                        }),
+                        non_empty: *non_empty,
+                        shape_name: None,
+                    },
+                    TAtomic::TVec {
+                        known_items,
+                        type_param,
+                        known_count,
+                        non_empty,
+                    } => TAtomic::TVec {
+                        known_items: known_items.as_ref().map(|known_items| {
+                            known_items
+                                .iter()
+                                .map(|(offset, (possibly_undefined, item_type))| {
+                                    (
+                                        *offset,
+                                        (*possibly_undefined, get_mapped_item_type(item_type)),
+                                    )
+                                })
+                                .collect()
+                        }),
+                        type_param: Box::new(if type_param.is_nothing() {
+                            get_nothing()
+                        } else {
+                            mapped_value_type.clone()
+                        }),
+                        known_count: *known_count,
+                        non_empty: *non_empty,
+                    },
+                    _ => {
+                        let (key_param, _) = get_arrayish_params(atomic, codebase)?;
+
+                        TAtomic::TDict {
+                            known_items: None,
+                            params: Some((
+                                Box::new(key_param),
+                                Box::new(mapped_value_type.clone()),
+                            )),
+                            non_empty: false,
+                            shape_name: None,
                         }
                     }
-                }
+                });
             }
 
-            None
+            Some(TUnion::new(new_types))
         }
-        &StrId::ASIO_JOIN => {
-            if args.len() == 1 {
-                let mut awaited_type = analysis_data
-                    .get_expr_type(args[0].1.pos())
-                    .cloned()
-                    .unwrap_or(get_mixed_any());
+        &StrId::ARRAY_CHUNK => {
+            let array_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
 
-                let awaited_types = awaited_type.types.drain(..).collect::<Vec<_>>();
+            let preserve_keys = if let Some((_, preserve_keys_arg)) = args.get(2) {
+                let preserve_keys_type = analysis_data.get_expr_type(preserve_keys_arg.pos())?;
 
-                let mut new_types = vec![];
+                if preserve_keys_type.is_true() {
+                    Some(true)
+                } else if preserve_keys_type.is_false() {
+                    Some(false)
+                } else {
+                    None
+                }
+            } else {
+                Some(false)
+            };
 
-                for atomic_type in awaited_types {
-                    if let TAtomic::TAwaitable { value } = atomic_type {
-                        let inside_type = (*value).clone();
-                        extend_dataflow_uniquely(
-                            &mut awaited_type.parent_nodes,
-                            inside_type.parent_nodes,
-                        );
-                        new_types.extend(inside_type.types);
+            let chunk_size = if let Some((_, size_arg)) = args.get(1) {
+                analysis_data
+                    .get_expr_type(size_arg.pos())
+                    .and_then(|t| t.get_single_literal_int_value())
+            } else {
+                None
+            };
 
-                        analysis_data.expr_effects.insert(
-                            (pos.start_offset() as u32, pos.end_offset() as u32),
-                            EFFECT_IMPURE,
-                        );
-                    } else {
-                        new_types.push(atomic_type);
+            if let (Some(preserve_keys), Some(chunk_size)) = (preserve_keys, chunk_size) {
+                if chunk_size > 0 && array_type.is_single() {
+                    if let Some(chunks) = get_literal_array_chunks(
+                        array_type.get_single(),
+                        chunk_size as usize,
+                        preserve_keys,
+                    ) {
+                        return Some(chunks);
                     }
                 }
+            }
 
-                awaited_type.types = new_types;
+            let mut key_type = None;
+            let mut value_type = None;
 
-                Some(awaited_type)
-            } else {
-                None
+            for atomic in &array_type.types {
+                let (key_param, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                key_type = Some(add_optional_union_type(
+                    key_param,
+                    key_type.as_ref(),
+                    codebase,
+                ));
+                value_type = Some(add_optional_union_type(
+                    value_param,
+                    value_type.as_ref(),
+                    codebase,
+                ));
             }
-        }
-        _ => None,
-    }
-}
+
+            let (key_type, value_type) = (key_type?, value_type?);
+
+            Some(get_vec(match preserve_keys {
+                Some(true) => get_dict(key_type, value_type),
+                Some(false) => get_vec(value_type),
+                // we don't know which, so chunks could be either
+                None => add_union_type(
+                    get_vec(value_type.clone()),
+                    &get_dict(key_type, value_type),
+                    codebase,
+                    false,
+                ),
+            }))
+        }
+        &StrId::LIB_VEC_SORT | &StrId::LIB_VEC_SORT_BY => {
+            let collection_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            get_sorted_vec_type(collection_type, codebase)
+        }
+        &StrId::LIB_DICT_FLIP | &StrId::ARRAY_FLIP => {
+            let array_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut new_types = vec![];
+
+            for atomic in &array_type.types {
+                new_types.push(get_flipped_array_type(atomic, codebase)?);
+            }
+
+            Some(TUnion::new(new_types))
+        }
+        &StrId::LIB_DICT_SELECT_KEYS => {
+            let dict_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+            let keys_type = analysis_data.get_expr_type(args.get(1)?.1.pos())?;
+
+            let selected_keys = get_selected_dict_keys(keys_type)?;
+
+            let mut new_types = vec![];
+
+            for atomic in &dict_type.types {
+                new_types.push(get_dict_with_selected_keys(atomic, &selected_keys)?);
+            }
+
+            Some(TUnion::new(new_types))
+        }
+        &StrId::LIB_VEC_REVERSE | &StrId::LIB_DICT_REVERSE | &StrId::ARRAY_REVERSE => {
+            let container_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            if container_type.types.len() != 1 {
+                return None;
+            }
+
+            let preserve_keys = if name == &StrId::ARRAY_REVERSE {
+                if let Some((_, preserve_keys_expr)) = args.get(1) {
+                    let preserve_keys_type =
+                        analysis_data.get_expr_type(preserve_keys_expr.pos())?;
+
+                    if preserve_keys_type.is_true() {
+                        true
+                    } else if preserve_keys_type.is_false() {
+                        false
+                    } else {
+                        return None;
+                    }
+                } else {
+                    false
+                }
+            } else {
+                name == &StrId::LIB_DICT_REVERSE
+            };
+
+            get_reversed_type(&container_type.types[0], preserve_keys)
+        }
+        &StrId::LIB_VEC_INTERSECT | &StrId::ARRAY_INTERSECT => {
+            let first_arg_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut key_type = get_nothing();
+            let mut value_type = get_nothing();
+
+            for atomic in &first_arg_type.types {
+                let (key_param, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                key_type = add_union_type(key_type, &key_param, codebase, false);
+                value_type = add_union_type(value_type, &value_param, codebase, false);
+            }
+
+            // any element may be missing from the other arguments, so the result
+            // can always be empty
+            Some(if name == &StrId::ARRAY_INTERSECT {
+                get_dict(key_type, value_type)
+            } else {
+                get_vec(value_type)
+            })
+        }
+        &StrId::LIB_VEC_FILTER_NULLS
+        | &StrId::LIB_DICT_FILTER_NULLS
+        | &StrId::LIB_KEYSET_FILTER_NULLS => {
+            let first_arg_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut key_type = get_nothing();
+            let mut value_type = get_nothing();
+
+            for atomic in &first_arg_type.types {
+                let (key_param, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                key_type = add_union_type(key_type, &key_param, codebase, false);
+                value_type = add_union_type(value_type, &value_param, codebase, false);
+            }
+
+            // every element could have been null, so the result can always be empty
+            let value_type = remove_null_from_element_type(value_type);
+
+            Some(match name {
+                &StrId::LIB_DICT_FILTER_NULLS => get_dict(key_type, value_type),
+                &StrId::LIB_KEYSET_FILTER_NULLS => get_keyset(value_type),
+                _ => get_vec(value_type),
+            })
+        }
+        &StrId::LIB_MATH_MAX_BY | &StrId::LIB_MATH_MIN_BY => {
+            let traversable_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut element_type = get_nothing();
+            let mut possibly_empty = false;
+
+            for atomic in &traversable_type.types {
+                let (_, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                if !atomic.is_non_empty_vec() && !atomic.is_non_empty_dict() {
+                    possibly_empty = true;
+                }
+
+                element_type = add_union_type(element_type, &value_param, codebase, false);
+            }
+
+            // null is only returned when there's nothing to pick from
+            Some(if element_type.is_nothing() {
+                get_null()
+            } else if possibly_empty {
+                add_union_type(element_type, &get_null(), codebase, false)
+            } else {
+                element_type
+            })
+        }
+        &StrId::LIB_KEYSET_FLATTEN => {
+            let traversable_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut element_type = get_nothing();
+
+            for atomic in &traversable_type.types {
+                let (_, container_type) = get_arrayish_params(atomic, codebase)?;
+
+                for container_atomic in &container_type.types {
+                    let (_, value_param) = get_arrayish_params(container_atomic, codebase)?;
+
+                    element_type = add_union_type(element_type, &value_param, codebase, false);
+                }
+            }
+
+            if element_type.is_nothing() {
+                return None;
+            }
+
+            Some(wrap_atomic(TAtomic::TKeyset {
+                type_param: Box::new(element_type),
+            }))
+        }
+        &StrId::LIB_KEYSET_CHUNK => {
+            let traversable_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+            let chunk_size = analysis_data
+                .get_expr_type(args.get(1)?.1.pos())?
+                .get_single_literal_int_value()?;
+
+            if chunk_size < 1 || !traversable_type.is_single() {
+                return None;
+            }
+
+            get_keyset_chunks(traversable_type.get_single(), chunk_size as usize)
+        }
+        &StrId::LIB_DICT_FLATTEN => {
+            let traversable_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            if traversable_type.types.len() != 1 {
+                return None;
+            }
+
+            get_flattened_dict(&traversable_type.types[0], codebase).map(wrap_atomic)
+        }
+        &StrId::LIB_DICT_DIFF_BY_KEY => {
+            // keys removed by an unpacked argument can't be tracked
+            if unpacked_arg.is_some() {
+                return None;
+            }
+
+            let dict_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut removed_keys = vec![];
+
+            for (_, arg_expr) in &args[1..] {
+                removed_keys.push(get_removed_dict_keys(
+                    analysis_data.get_expr_type(arg_expr.pos())?,
+                )?);
+            }
+
+            let mut new_types = vec![];
+
+            for atomic in &dict_type.types {
+                new_types.push(get_dict_without_keys(atomic, &removed_keys)?);
+            }
+
+            Some(TUnion::new(new_types))
+        }
+        &StrId::JSON_DECODE => {
+            // options like JSON_FB_HACK_ARRAYS change the shape of the result
+            if args.len() != 2 {
+                return None;
+            }
+
+            let json_string = analysis_data
+                .get_expr_type(args[0].1.pos())?
+                .get_single_literal_string_value()?;
+
+            // without $assoc, objects are decoded as stdClass
+            if !analysis_data.get_expr_type(args[1].1.pos())?.is_true() {
+                return None;
+            }
+
+            get_decoded_json_type(&serde_json::from_str(&json_string).ok()?)
+        }
+        &StrId::LIB_KEYSET_FILTER => {
+            let collection_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut value_type = None;
+
+            for atomic in &collection_type.types {
+                let (_, value_param) = get_arrayish_params(atomic, codebase)?;
+
+                value_type = Some(add_optional_union_type(
+                    value_param,
+                    value_type.as_ref(),
+                    codebase,
+                ));
+            }
+
+            Some(wrap_atomic(TAtomic::TKeyset {
+                type_param: Box::new(value_type?),
+            }))
+        }
+        &StrId::LIB_KEYSET_MAP | &StrId::LIB_KEYSET_MAP_WITH_KEY => {
+            let mapped_type =
+                get_callable_return_type(statements_analyzer, &args.get(1)?.1, analysis_data)?;
+
+            get_keyset_map_type(mapped_type, codebase)
+        }
+        &StrId::LIB_KEYSET_MAP_ASYNC => {
+            let mapped_type = get_awaited_type(
+                &get_callable_return_type(statements_analyzer, &args.get(1)?.1, analysis_data)?,
+                codebase,
+            )?;
+
+            Some(wrap_atomic(TAtomic::TAwaitable {
+                value: Box::new(get_keyset_map_type(mapped_type, codebase)?),
+            }))
+        }
+        &StrId::LIB_DICT_MAP | &StrId::LIB_DICT_MAP_WITH_KEY => {
+            if let (Some((_, collection_arg)), Some((_, callable_arg))) =
+                (args.first(), args.get(1))
+            {
+                let mapped_value_type =
+                    get_callable_return_type(statements_analyzer, callable_arg, analysis_data)?;
+
+                let collection_type = analysis_data.get_expr_type(collection_arg.pos())?;
+
+                get_dict_map_type(collection_type, &mapped_value_type, codebase)
+            } else {
+                None
+            }
+        }
+        &StrId::LIB_DICT_MAP_ASYNC | &StrId::LIB_DICT_MAP_WITH_KEY_ASYNC => {
+            if let (Some((_, collection_arg)), Some((_, callable_arg))) =
+                (args.first(), args.get(1))
+            {
+                let mapped_value_type = get_awaited_type(
+                    &get_callable_return_type(statements_analyzer, callable_arg, analysis_data)?,
+                    codebase,
+                )?;
+
+                let collection_type = analysis_data.get_expr_type(collection_arg.pos())?;
+
+                Some(wrap_atomic(TAtomic::TAwaitable {
+                    value: Box::new(get_dict_map_type(
+                        collection_type,
+                        &mapped_value_type,
+                        codebase,
+                    )?),
+                }))
+            } else {
+                None
+            }
+        }
+        &StrId::LIB_DICT_FROM_ASYNC => {
+            let collection_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut new_types = vec![];
+
+            // only dicts with known keys are more precise than the declared signature
+            for atomic in &collection_type.types {
+                if let TAtomic::TDict {
+                    known_items: Some(known_items),
+                    params,
+                    non_empty,
+                    ..
+                } = atomic
+                {
+                    let mut new_known_items = BTreeMap::new();
+
+                    for (key, (possibly_undefined, item_type)) in known_items {
+                        new_known_items.insert(
+                            key.clone(),
+                            (
+                                *possibly_undefined,
+                                Arc::new(get_awaited_type(item_type, codebase)?),
+                            ),
+                        );
+                    }
+
+                    new_types.push(TAtomic::TDict {
+                        known_items: Some(new_known_items),
+                        params: if let Some((key_param, value_param)) = params {
+                            Some((
+                                key_param.clone(),
+                                Box::new(get_awaited_type(value_param, codebase)?),
+                            ))
+                        } else {
+                            None
+                        },
+                        non_empty: *non_empty,
+                        shape_name: None,
+                    });
+                } else {
+                    return None;
+                }
+            }
+
+            Some(wrap_atomic(TAtomic::TAwaitable {
+                value: Box::new(TUnion::new(new_types)),
+            }))
+        }
+        &StrId::PREG_MATCH | &StrId::PREG_MATCH_WITH_MATCHES => {
+            if let (Some((_, pattern_arg)), Some((_, subject_arg))) = (args.first(), args.get(1)) {
+                let pattern = analysis_data
+                    .get_expr_type(pattern_arg.pos())
+                    .and_then(|t| t.get_single_literal_string_value());
+                let subject = analysis_data
+                    .get_expr_type(subject_arg.pos())
+                    .and_then(|t| t.get_single_literal_string_value());
+
+                if let (Some(pattern), Some(subject)) = (pattern, subject) {
+                    if let Some(matches) = get_literal_preg_match_result(&pattern, &subject) {
+                        return Some(get_literal_int(if matches { 1 } else { 0 }));
+                    }
+                }
+            }
+
+            let mut match_result = TUnion::new(vec![
+                TAtomic::TLiteralInt { value: 0 },
+                TAtomic::TLiteralInt { value: 1 },
+                TAtomic::TFalse,
+            ]);
+            match_result.ignore_falsable_issues = true;
+            Some(match_result)
+        }
+        &StrId::LIB_STR_SEARCH
+        | &StrId::LIB_STR_SEARCH_CI
+        | &StrId::LIB_STR_SEARCH_LAST
+        | &StrId::STRPOS
+        | &StrId::STRIPOS
+        | &StrId::STRRPOS => {
+            // HSL functions return null when the needle isn't found, the legacy ones false
+            let not_found_type = match name {
+                &StrId::STRPOS | &StrId::STRIPOS | &StrId::STRRPOS => get_false(),
+                _ => get_null(),
+            };
+
+            if let Some(position) = get_literal_search_position(name, args, analysis_data) {
+                return Some(if let Some(position) = position {
+                    get_literal_int(position as i64)
+                } else {
+                    not_found_type
+                });
+            }
+
+            Some(add_union_type(get_int(), &not_found_type, codebase, false))
+        }
+        // even zero, or a negative number of decimals, is formatted as at least "0"
+        &StrId::NUMBER_FORMAT | &StrId::LIB_STR_FORMAT_NUMBER => {
+            Some(wrap_atomic(TAtomic::TStringWithFlags(false, true, false)))
+        }
+        &StrId::WORDWRAP => {
+            let text = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_string_value()?;
+
+            let width = if let Some((_, arg_expr)) = args.get(1) {
+                analysis_data
+                    .get_expr_type(arg_expr.pos())?
+                    .get_single_literal_int_value()?
+            } else {
+                75
+            };
+
+            let break_chars = if let Some((_, arg_expr)) = args.get(2) {
+                analysis_data
+                    .get_expr_type(arg_expr.pos())?
+                    .get_single_literal_string_value()?
+            } else {
+                "\n".to_string()
+            };
+
+            let cut = if let Some((_, arg_expr)) = args.get(3) {
+                let cut_type = analysis_data.get_expr_type(arg_expr.pos())?;
+
+                if cut_type.is_true() {
+                    true
+                } else if cut_type.is_false() {
+                    false
+                } else {
+                    return None;
+                }
+            } else {
+                false
+            };
+
+            // these throw at runtime
+            if width < 0 || break_chars.is_empty() || (width == 0 && cut) {
+                return None;
+            }
+
+            let wrapped = wrap_words(text.as_bytes(), width as usize, break_chars.as_bytes(), cut);
+
+            Some(get_literal_string(String::from_utf8(wrapped).ok()?))
+        }
+        &StrId::CHUNK_SPLIT => {
+            let text = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_string_value()?;
+
+            let length = if let Some((_, arg_expr)) = args.get(1) {
+                analysis_data
+                    .get_expr_type(arg_expr.pos())?
+                    .get_single_literal_int_value()?
+            } else {
+                76
+            };
+
+            let separator = if let Some((_, arg_expr)) = args.get(2) {
+                analysis_data
+                    .get_expr_type(arg_expr.pos())?
+                    .get_single_literal_string_value()?
+            } else {
+                "\r\n".to_string()
+            };
+
+            if length < 1 {
+                return None;
+            }
+
+            // every chunk is followed by the separator, including the last one, and an
+            // empty string still gets a single separator
+            let mut chunked = vec![];
+
+            for chunk in text.as_bytes().chunks(length as usize) {
+                chunked.extend_from_slice(chunk);
+                chunked.extend_from_slice(separator.as_bytes());
+            }
+
+            if text.is_empty() {
+                chunked.extend_from_slice(separator.as_bytes());
+            }
+
+            Some(get_literal_string(String::from_utf8(chunked).ok()?))
+        }
+        &StrId::HEXDEC | &StrId::OCTDEC | &StrId::BINDEC => {
+            let value = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_string_value()?;
+
+            let radix = match name {
+                &StrId::HEXDEC => 16,
+                &StrId::OCTDEC => 8,
+                _ => 2,
+            };
+
+            // invalid digits are ignored, and results past the int range become floats
+            let mut result = 0u128;
+
+            for digit in value.chars().filter_map(|c| c.to_digit(radix)) {
+                result = result * radix as u128 + digit as u128;
+
+                if result > i64::MAX as u128 {
+                    return Some(get_float());
+                }
+            }
+
+            Some(get_literal_int(result as i64))
+        }
+        &StrId::DECHEX | &StrId::DECOCT | &StrId::DECBIN => {
+            let value = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_int_value()?;
+
+            // negative numbers are converted as unsigned
+            let value = value as u64;
+
+            Some(get_literal_string(match name {
+                &StrId::DECHEX => format!("{:x}", value),
+                &StrId::DECOCT => format!("{:o}", value),
+                _ => format!("{:b}", value),
+            }))
+        }
+        // Str\length is byte-based just like strlen (Str\length_l is the
+        // locale-aware variant), so both report the UTF-8 byte count
+        &StrId::LIB_STR_LENGTH | &StrId::STRLEN => {
+            let string_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut lengths = vec![];
+
+            for atomic in &string_type.types {
+                if let TAtomic::TLiteralString { value } = atomic {
+                    let length = TAtomic::TLiteralInt {
+                        value: value.len() as i64,
+                    };
+
+                    if !lengths.contains(&length) {
+                        lengths.push(length);
+                    }
+                } else {
+                    return None;
+                }
+            }
+
+            if lengths.is_empty() {
+                None
+            } else {
+                Some(TUnion::new(lengths))
+            }
+        }
+        &StrId::PREG_QUOTE => {
+            let input = analysis_data
+                .get_expr_type(args.first()?.1.pos())?
+                .get_single_literal_string_value()?;
+
+            let delimiter = if let Some((_, delimiter_arg)) = args.get(1) {
+                analysis_data
+                    .get_expr_type(delimiter_arg.pos())?
+                    .get_single_literal_string_value()?
+                    .chars()
+                    .next()
+            } else {
+                None
+            };
+
+            Some(get_literal_string(get_preg_quoted_string(
+                &input, delimiter,
+            )))
+        }
+        &StrId::GETTYPE => {
+            let arg_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+            let mut type_names = vec![];
+
+            for atomic in &arg_type.types {
+                for type_name in get_gettype_names(atomic) {
+                    if !type_names.contains(&type_name) {
+                        type_names.push(type_name);
+                    }
+                }
+            }
+
+            Some(TUnion::new(
+                type_names
+                    .into_iter()
+                    .map(|type_name| TAtomic::TLiteralString {
+                        value: type_name.to_string(),
+                    })
+                    .collect(),
+            ))
+        }
+        &StrId::IDX_FN => {
+            if args.len() >= 2 {
+                let dict_type = analysis_data.get_rc_expr_type(args[0].1.pos()).cloned();
+                let dim_type = analysis_data.get_rc_expr_type(args[1].1.pos()).cloned();
+
+                let mut expr_type = None;
+
+                if let (Some(dict_type), Some(dim_type)) = (dict_type, dim_type) {
+                    for atomic_type in &dict_type.types {
+                        if let TAtomic::TDict { .. } = atomic_type {
+                            let mut expr_type_inner = handle_array_access_on_dict(
+                                statements_analyzer,
+                                pos,
+                                analysis_data,
+                                context,
+                                atomic_type,
+                                &dim_type,
+                                false,
+                                &mut false,
+                                true,
+                                &mut false,
+                                &mut false,
+                            );
+
+                            if args.len() == 2 && !expr_type_inner.is_mixed() {
+                                expr_type_inner =
+                                    add_union_type(expr_type_inner, &get_null(), codebase, false);
+                            }
+
+                            expr_type = Some(expr_type_inner);
+                        }
+                    }
+
+                    if args.len() > 2 {
+                        let default_type = analysis_data.get_expr_type(args[2].1.pos());
+                        expr_type = expr_type.map(|expr_type| {
+                            if let Some(default_type) = default_type {
+                                add_union_type(expr_type, default_type, codebase, false)
+                            } else {
+                                add_union_type(expr_type, &get_mixed_any(), codebase, false)
+                            }
+                        });
+                    }
+                }
+
+                Some(expr_type.unwrap_or(get_mixed_any()))
+            } else {
+                None
+            }
+        }
+        &StrId::DIRNAME => {
+            if args.len() == 1 {
+                let file_type = analysis_data.get_rc_expr_type(args[0].1.pos()).cloned();
+
+                if let Some(file_type) = file_type {
+                    if let Some(literal_value) = file_type.get_single_literal_string_value() {
+                        let path = Path::new(&literal_value);
+                        if let Some(dir) = path.parent() {
+                            return Some(get_literal_string(dir.to_str().unwrap().to_owned()));
+                        }
+                    }
+                }
+            }
+
+            None
+        }
+        &StrId::ASIO_JOIN => {
+            if args.len() == 1 {
+                let mut awaited_type = analysis_data
+                    .get_expr_type(args[0].1.pos())
+                    .cloned()
+                    .unwrap_or(get_mixed_any());
+
+                let awaited_types = awaited_type.types.drain(..).collect::<Vec<_>>();
+
+                let mut new_types = vec![];
+
+                for atomic_type in awaited_types {
+                    if let TAtomic::TAwaitable { value } = atomic_type {
+                        let inside_type = (*value).clone();
+                        extend_dataflow_uniquely(
+                            &mut awaited_type.parent_nodes,
+                            inside_type.parent_nodes,
+                        );
+                        new_types.extend(inside_type.types);
+
+                        analysis_data.expr_effects.insert(
+                            (pos.start_offset() as u32, pos.end_offset() as u32),
+                            EFFECT_IMPURE,
+                        );
+                    } else {
+                        new_types.push(atomic_type);
+                    }
+                }
+
+                awaited_type.types = new_types;
+
+                Some(awaited_type)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
 
 fn handle_str_format(
     simple_string: &BString,
     first_arg: &(ast_defs::ParamKind, aast::Expr<(), ()>),
     args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
-    statements_analyzer: &StatementsAnalyzer<'_>,
+    statements_analyzer: &StatementsAnalyzer<'_>,
+    analysis_data: &mut FunctionAnalysisData,
+    pos: &Pos,
+) -> TUnion {
+    let mut escaped = false;
+    let mut in_format_string = false;
+    let mut literals = vec![];
+    let mut cur_literal = "".to_string();
+
+    for c in simple_string.iter().copied() {
+        if in_format_string {
+            in_format_string = false;
+            continue;
+        }
+
+        if !escaped {
+            if c as char == '%' {
+                in_format_string = true;
+                literals.push(aast::Expr(
+                    (),
+                    first_arg.1.pos().clone(),
+                    aast::Expr_::String(BString::from(cur_literal)),
+                ));
+                cur_literal = "".to_string();
+                continue;
+            }
+
+            if c as char == '\\' {
+                escaped = true;
+            }
+
+            in_format_string = false;
+        } else {
+            if c as char == '\\' {
+                cur_literal += "\\";
+                escaped = false;
+                continue;
+            }
+
+            escaped = false;
+        }
+
+        cur_literal += (c as char).to_string().as_str();
+    }
+
+    literals.push(aast::Expr(
+        (),
+        first_arg.1.pos().clone(),
+        aast::Expr_::String(BString::from(cur_literal)),
+    ));
+
+    let mut concat_args = vec![];
+
+    for (i, literal) in literals.iter().enumerate() {
+        concat_args.push(literal);
+        if let Some(arg) = args.get(i + 1) {
+            concat_args.push(&arg.1);
+        } else {
+            break;
+        }
+    }
+
+    analyze_concat_nodes(concat_args, statements_analyzer, analysis_data, pos)
+}
+
+/// A port of PHP's wordwrap, which works on bytes and only breaks at spaces
+/// unless `cut` is set
+fn wrap_words(text: &[u8], width: usize, break_chars: &[u8], cut: bool) -> Vec<u8> {
+    if text.is_empty() {
+        return vec![];
+    }
+
+    let mut last_start = 0;
+    let mut last_space = 0;
+
+    // a single-character break can be swapped in for a space in place
+    if break_chars.len() == 1 && !cut {
+        let break_char = break_chars[0];
+        let mut wrapped = text.to_vec();
+
+        for (current, &byte) in text.iter().enumerate() {
+            if byte == break_char {
+                last_start = current + 1;
+                last_space = current + 1;
+            } else if byte == b' ' {
+                if current - last_start >= width {
+                    wrapped[current] = break_char;
+                    last_start = current + 1;
+                }
+
+                last_space = current;
+            } else if current - last_start >= width && last_start != last_space {
+                wrapped[last_space] = break_char;
+                last_start = last_space + 1;
+            }
+        }
+
+        return wrapped;
+    }
+
+    let mut wrapped = vec![];
+    let mut current = 0;
+
+    while current < text.len() {
+        if current + break_chars.len() < text.len() && text[current..].starts_with(break_chars) {
+            wrapped.extend_from_slice(&text[last_start..current + break_chars.len()]);
+            current += break_chars.len() - 1;
+            last_start = current + 1;
+            last_space = current + 1;
+        } else if text[current] == b' ' {
+            if current - last_start >= width {
+                wrapped.extend_from_slice(&text[last_start..current]);
+                wrapped.extend_from_slice(break_chars);
+                last_start = current + 1;
+            }
+
+            last_space = current;
+        } else if current - last_start >= width && cut && last_start >= last_space {
+            wrapped.extend_from_slice(&text[last_start..current]);
+            wrapped.extend_from_slice(break_chars);
+            last_start = current;
+            last_space = current;
+        } else if current - last_start >= width && last_start < last_space {
+            wrapped.extend_from_slice(&text[last_start..last_space]);
+            wrapped.extend_from_slice(break_chars);
+            last_start = last_space + 1;
+            last_space = last_start;
+        }
+
+        current += 1;
+    }
+
+    if last_start < text.len() {
+        wrapped.extend_from_slice(&text[last_start..]);
+    }
+
+    wrapped
+}
+
+/// Finds a literal needle in a literal haystack, returning Some(None) when it's
+/// definitely not there. Negative offsets and empty needles aren't folded.
+fn get_literal_search_position(
+    name: &StrId,
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    analysis_data: &FunctionAnalysisData,
+) -> Option<Option<usize>> {
+    let mut haystack = analysis_data
+        .get_expr_type(args.first()?.1.pos())?
+        .get_single_literal_string_value()?;
+    let mut needle = analysis_data
+        .get_expr_type(args.get(1)?.1.pos())?
+        .get_single_literal_string_value()?;
+
+    let offset = if let Some((_, offset_expr)) = args.get(2) {
+        analysis_data
+            .get_expr_type(offset_expr.pos())?
+            .get_single_literal_int_value()?
+    } else {
+        0
+    };
+
+    if needle.is_empty() || offset < 0 || offset as usize > haystack.len() {
+        return None;
+    }
+
+    if matches!(name, &StrId::LIB_STR_SEARCH_CI | &StrId::STRIPOS) {
+        // case folding is only byte-for-byte for ASCII
+        if !haystack.is_ascii() || !needle.is_ascii() {
+            return None;
+        }
+
+        haystack = haystack.to_ascii_lowercase();
+        needle = needle.to_ascii_lowercase();
+    }
+
+    let offset = offset as usize;
+
+    let position = match name {
+        &StrId::LIB_STR_SEARCH_LAST | &StrId::STRRPOS => haystack.as_bytes()[offset..]
+            .windows(needle.len())
+            .rposition(|window| window == needle.as_bytes()),
+        _ => haystack.as_bytes()[offset..]
+            .windows(needle.len())
+            .position(|window| window == needle.as_bytes()),
+    };
+
+    Some(position.map(|position| position + offset))
+}
+
+// Only formats made up of plain `%s` and `%d` conversions are folded, since
+// flags, widths and precisions aren't understood here
+fn get_printf_output_length(
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    analysis_data: &FunctionAnalysisData,
+) -> Option<usize> {
+    let first_arg = args.first()?;
+
+    let format = if let aast::Expr_::String(format) = &first_arg.1 .2 {
+        format
+    } else {
+        return None;
+    };
+
+    let mut output_length = 0;
+    let mut arg_offset = 1;
+    let mut format_chars = format.iter().copied();
+
+    while let Some(c) = format_chars.next() {
+        match c {
+            b'%' => {
+                let arg_type = analysis_data.get_expr_type(args.get(arg_offset)?.1.pos())?;
+
+                output_length += match format_chars.next()? {
+                    b'd' => arg_type.get_single_literal_int_value()?.to_string().len(),
+                    b's' => {
+                        if let Some(int_value) = arg_type.get_single_literal_int_value() {
+                            int_value.to_string().len()
+                        } else {
+                            arg_type.get_single_literal_string_value()?.len()
+                        }
+                    }
+                    _ => return None,
+                };
+
+                arg_offset += 1;
+            }
+            b'\\' => return None,
+            _ => {
+                output_length += 1;
+            }
+        }
+    }
+
+    if arg_offset != args.len() {
+        return None;
+    }
+
+    Some(output_length)
+}
+
+/// Mirrors preg_quote, which also escapes the first character of the delimiter.
+fn get_preg_quoted_string(input: &str, delimiter: Option<char>) -> String {
+    let mut quoted = String::with_capacity(input.len());
+
+    for c in input.chars() {
+        match c {
+            '.' | '\\' | '+' | '*' | '?' | '[' | '^' | ']' | '$' | '(' | ')' | '{' | '}' | '='
+            | '!' | '>' | '<' | '|' | ':' | '-' | '#' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\0' => {
+                quoted.push_str("\\000");
+            }
+            _ => {
+                if Some(c) == delimiter {
+                    quoted.push('\\');
+                }
+                quoted.push(c);
+            }
+        }
+    }
+
+    quoted
+}
+
+/// Evaluates a PCRE pattern against a subject when both are known at analysis time.
+/// Returns None for patterns whose meaning differs between PCRE and the regex crate.
+fn get_literal_preg_match_result(pattern: &str, subject: &str) -> Option<bool> {
+    let (inner_pattern, modifiers) = split_preg_pattern(pattern)?;
+
+    // without the D modifier PCRE's $ also matches before a trailing newline, and in
+    // UTF-8 mode . matches a whole character rather than a single byte
+    if modifiers.contains('u') || (!modifiers.contains('D') && has_unescaped_dollar(inner_pattern))
+    {
+        return None;
+    }
+
+    Some(get_regex_from_preg_pattern(pattern)?.is_match(subject.as_bytes()))
+}
+
+fn has_unescaped_dollar(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '$' => return true,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Types the `$matches` out-param of `preg_match_all_with_matches` when the pattern is
+/// a literal. With PREG_PATTERN_ORDER (the default) there's one vec of matches per
+/// capture group, and with PREG_SET_ORDER one shape of capture groups per match.
+/// Named groups appear under both their name and their offset.
+///
+/// This is only applied when analyzing function bodies. Taint analysis keeps the
+/// declared type, so the two modes can see different types for the same `$matches`.
+pub(crate) fn get_preg_match_all_matches_type(
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    analysis_data: &FunctionAnalysisData,
+) -> Option<TUnion> {
+    let pattern = analysis_data
+        .get_expr_type(args.first()?.1.pos())?
+        .get_single_literal_string_value()?;
+
+    let set_order = if let Some((_, flags_arg)) = args.get(3) {
+        let flags = if let aast::Expr_::Id(id) = &flags_arg.2 {
+            match id.1.trim_start_matches('\\') {
+                "PREG_PATTERN_ORDER" => 1,
+                "PREG_SET_ORDER" => 2,
+                _ => return None,
+            }
+        } else {
+            analysis_data
+                .get_expr_type(flags_arg.pos())?
+                .get_single_literal_int_value()?
+        };
+
+        match flags {
+            0 | 1 => false,
+            2 => true,
+            // offset capture and unmatched-as-null change the shape of each match
+            _ => return None,
+        }
+    } else {
+        false
+    };
+
+    let regex = get_regex_from_preg_pattern(&pattern)?;
+
+    let optional_groups = if set_order {
+        let (inner_pattern, modifiers) = split_preg_pattern(&pattern)?;
+
+        // in extended mode whitespace can sit between a group and its quantifier
+        if modifiers.contains('x') {
+            return None;
+        }
+
+        let optional_groups = get_optional_capture_groups(inner_pattern)?;
+
+        if optional_groups.len() != regex.captures_len() {
+            return None;
+        }
+
+        optional_groups
+    } else {
+        vec![]
+    };
+
+    let group_type = if set_order {
+        get_string()
+    } else {
+        get_vec(get_string())
+    };
+
+    let mut group_items = BTreeMap::new();
+
+    for (offset, name) in regex.capture_names().enumerate() {
+        // in set order PHP leaves out unmatched groups at the end of each match, but
+        // unmatched groups before a matched one are kept as empty strings
+        let possibly_undefined = set_order && optional_groups[offset..].iter().all(|o| *o);
+
+        group_items.insert(
+            DictKey::Int(offset as u64),
+            (possibly_undefined, Arc::new(group_type.clone())),
+        );
+
+        if let Some(name) = name {
+            group_items.insert(
+                DictKey::String(name.to_string()),
+                (possibly_undefined, Arc::new(group_type.clone())),
+            );
+        }
+    }
+
+    let groups_type = wrap_atomic(TAtomic::TDict {
+        known_items: Some(group_items),
+        params: None,
+        non_empty: true,
+        shape_name: None,
+    });
+
+    Some(if set_order {
+        get_vec(groups_type)
+    } else {
+        groups_type
+    })
+}
+
+/// Works out which capture groups in a PCRE pattern can go unmatched, because they or
+/// a group around them can repeat zero times. Offset 0 is the whole match. Returns None
+/// for patterns with alternation, where any group might go unmatched.
+fn get_optional_capture_groups(pattern: &str) -> Option<Vec<bool>> {
+    let mut optional_groups = vec![false];
+
+    // for each open group, the offset its own (and any nested) capture groups start at
+    let mut open_groups = vec![];
+
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => {
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                }
+
+                // a ] straight after the opening bracket is part of the class
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                }
+
+                loop {
+                    match chars.next()? {
+                        '\\' => {
+                            chars.next();
+                        }
+                        ']' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '|' => return None,
+            '(' => {
+                open_groups.push(optional_groups.len());
+
+                let is_capturing = if chars.peek() == Some(&'?') {
+                    let mut lookahead = chars.clone();
+                    lookahead.next();
+
+                    match lookahead.next() {
+                        Some('P') => !matches!(lookahead.next(), Some('=' | '>')),
+                        Some('<') => !matches!(lookahead.next(), Some('=' | '!')),
+                        Some('\'') => true,
+                        _ => false,
+                    }
+                } else {
+                    true
+                };
+
+                if is_capturing {
+                    optional_groups.push(false);
+                }
+            }
+            ')' => {
+                let first_group = open_groups.pop()?;
+
+                let can_repeat_zero_times = match chars.peek() {
+                    Some('?' | '*') => true,
+                    Some('{') => {
+                        let mut lookahead = chars.clone();
+                        lookahead.next();
+                        matches!(lookahead.next(), Some('0' | ','))
+                    }
+                    _ => false,
+                };
+
+                if can_repeat_zero_times {
+                    for optional_group in &mut optional_groups[first_group..] {
+                        *optional_group = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !open_groups.is_empty() {
+        return None;
+    }
+
+    Some(optional_groups)
+}
+
+/// Splits a delimited PCRE pattern like `/abc/i` into the pattern and its modifiers
+fn split_preg_pattern(pattern: &str) -> Option<(&str, &str)> {
+    let delimiter = pattern.chars().next()?;
+
+    if delimiter.is_alphanumeric() || delimiter == '\\' || delimiter.is_whitespace() {
+        return None;
+    }
+
+    let closing_delimiter = match delimiter {
+        '(' => ')',
+        '{' => '}',
+        '[' => ']',
+        '<' => '>',
+        c => c,
+    };
+
+    let pattern_end = pattern.rfind(closing_delimiter)?;
+
+    if pattern_end == 0 {
+        return None;
+    }
+
+    Some((
+        &pattern[delimiter.len_utf8()..pattern_end],
+        &pattern[pattern_end + closing_delimiter.len_utf8()..],
+    ))
+}
+
+fn get_regex_from_preg_pattern(pattern: &str) -> Option<regex::bytes::Regex> {
+    let (inner_pattern, modifiers) = split_preg_pattern(pattern)?;
+
+    let mut inline_flags = String::new();
+
+    for modifier in modifiers.chars() {
+        match modifier {
+            'i' | 'm' | 's' | 'x' => inline_flags.push(modifier),
+            'u' | 'D' => {}
+            _ => return None,
+        }
+    }
+
+    // older versions of the regex crate only understand the (?P<name>...) group syntax
+    let inner_pattern = get_python_style_named_groups(inner_pattern);
+
+    // PCRE's \w, \d and \s only match ASCII, so Unicode classes are turned off
+    regex::bytes::Regex::new(&format!("(?{}-u){}", inline_flags, inner_pattern)).ok()
+}
+
+fn get_python_style_named_groups(pattern: &str) -> String {
+    let mut converted = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        converted.push(c);
+
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                converted.push(escaped);
+            }
+        } else if c == '(' && chars.peek() == Some(&'?') {
+            converted.push(chars.next().unwrap());
+
+            if chars.peek() == Some(&'<') {
+                chars.next();
+
+                // lookbehinds start with (?<= or (?<!
+                if !matches!(chars.peek(), Some('=') | Some('!')) {
+                    converted.push('P');
+                }
+
+                converted.push('<');
+            }
+        }
+    }
+
+    converted
+}
+
+/// Returns the declared or inferred return type of a closure, or of a function
+/// or method referenced by a closure alias
+pub(crate) fn get_callable_return_type(
+    statements_analyzer: &StatementsAnalyzer,
+    callable_expr: &aast::Expr<(), ()>,
     analysis_data: &mut FunctionAnalysisData,
-    pos: &Pos,
-) -> TUnion {
-    let mut escaped = false;
-    let mut in_format_string = false;
-    let mut literals = vec![];
-    let mut cur_literal = "".to_string();
+) -> Option<TUnion> {
+    let callable_type = analysis_data.get_expr_type(callable_expr.pos())?.clone();
+    let codebase = statements_analyzer.get_codebase();
+
+    let mut return_type = None;
+
+    for atomic in callable_type.types {
+        let atomic = if let TAtomic::TClosureAlias { id } = &atomic {
+            type_expander::get_closure_from_id(
+                id,
+                codebase,
+                &Some(statements_analyzer.get_interner()),
+                &mut analysis_data.data_flow_graph,
+            )?
+        } else {
+            atomic
+        };
+
+        if let TAtomic::TClosure {
+            return_type: Some(closure_return_type),
+            ..
+        } = atomic
+        {
+            return_type = Some(add_optional_union_type(
+                *closure_return_type,
+                return_type.as_ref(),
+                codebase,
+            ));
+        } else {
+            return None;
+        }
+    }
+
+    return_type
+}
+
+/// Returns the parameter name and returned expression of a one-parameter lambda whose
+/// body is a single return statement, e.g. `$x ==> $x + 1`
+fn get_single_expression_lambda(
+    callable_expr: &aast::Expr<(), ()>,
+) -> Option<(&str, &aast::Expr<(), ()>)> {
+    let fun = match &callable_expr.2 {
+        aast::Expr_::Lfun(boxed) => &boxed.0,
+        aast::Expr_::Efun(boxed) => &boxed.fun,
+        _ => return None,
+    };
+
+    if fun.params.len() != 1 || fun.params[0].is_variadic || fun.body.fb_ast.0.len() != 1 {
+        return None;
+    }
+
+    if let aast::Stmt_::Return(boxed) = &fun.body.fb_ast.0[0].1 {
+        if let Some(return_expr) = boxed.as_ref() {
+            return Some((fun.params[0].name.as_str(), return_expr));
+        }
+    }
+
+    None
+}
+
+/// Evaluates a lambda body on a literal input. Only the parameter itself, literals,
+/// integer arithmetic and string concatenation are supported, so anything with
+/// branches falls back to the lambda's return type.
+fn evaluate_literal_lambda_body(
+    expr: &aast::Expr<(), ()>,
+    param_name: &str,
+    input: &TAtomic,
+) -> Option<TAtomic> {
+    match &expr.2 {
+        aast::Expr_::Lvar(lid) if lid.1 .1 == param_name => match input {
+            TAtomic::TLiteralInt { .. } | TAtomic::TLiteralString { .. } => Some(input.clone()),
+            _ => None,
+        },
+        aast::Expr_::Int(value) => Some(TAtomic::TLiteralInt {
+            value: int_from_string(value).ok()?,
+        }),
+        aast::Expr_::String(value) => Some(TAtomic::TLiteralString {
+            value: value.to_string(),
+        }),
+        aast::Expr_::Binop(boxed) => {
+            let lhs = evaluate_literal_lambda_body(&boxed.lhs, param_name, input)?;
+            let rhs = evaluate_literal_lambda_body(&boxed.rhs, param_name, input)?;
+
+            match (&boxed.bop, lhs, rhs) {
+                (
+                    ast_defs::Bop::Plus | ast_defs::Bop::Minus | ast_defs::Bop::Star,
+                    TAtomic::TLiteralInt { value: lhs_value },
+                    TAtomic::TLiteralInt { value: rhs_value },
+                ) => Some(TAtomic::TLiteralInt {
+                    // overflowing results are floats at runtime
+                    value: match &boxed.bop {
+                        ast_defs::Bop::Plus => lhs_value.checked_add(rhs_value),
+                        ast_defs::Bop::Minus => lhs_value.checked_sub(rhs_value),
+                        _ => lhs_value.checked_mul(rhs_value),
+                    }?,
+                }),
+                (ast_defs::Bop::Dot, lhs, rhs) => Some(TAtomic::TLiteralString {
+                    value: get_literal_concat_operand(&lhs)? + &get_literal_concat_operand(&rhs)?,
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn get_literal_concat_operand(atomic: &TAtomic) -> Option<String> {
+    match atomic {
+        TAtomic::TLiteralInt { value } => Some(value.to_string()),
+        TAtomic::TLiteralString { value } => Some(value.clone()),
+        _ => None,
+    }
+}
+
+/// Keysets can only hold arraykeys, so anything else falls back to the declared signature
+/// (and is reported by the function call analyzer).
+fn get_keyset_map_type(mapped_type: TUnion, codebase: &CodebaseInfo) -> Option<TUnion> {
+    if !union_type_comparator::is_contained_by(
+        codebase,
+        &mapped_type,
+        &get_arraykey(false),
+        false,
+        false,
+        false,
+        &mut TypeComparisonResult::new(),
+    ) {
+        return None;
+    }
+
+    Some(wrap_atomic(TAtomic::TKeyset {
+        type_param: Box::new(mapped_type),
+    }))
+}
+
+/// Retypes the values of a collection passed to Dict\map or Dict\map_with_key,
+/// keeping its keys (including the known keys of shapes and tuples)
+fn get_dict_map_type(
+    collection_type: &TUnion,
+    mapped_value_type: &TUnion,
+    codebase: &CodebaseInfo,
+) -> Option<TUnion> {
+    let mut new_types = vec![];
+
+    for atomic in &collection_type.types {
+        new_types.push(match atomic {
+            TAtomic::TDict {
+                known_items,
+                params,
+                non_empty,
+                ..
+            } => TAtomic::TDict {
+                known_items: known_items.as_ref().map(|known_items| {
+                    known_items
+                        .iter()
+                        .map(|(key, (possibly_undefined, _))| {
+                            (
+                                key.clone(),
+                                (*possibly_undefined, Arc::new(mapped_value_type.clone())),
+                            )
+                        })
+                        .collect()
+                }),
+                params: params
+                    .as_ref()
+                    .map(|(key_param, _)| (key_param.clone(), Box::new(mapped_value_type.clone()))),
+                non_empty: *non_empty,
+                shape_name: None,
+            },
+            TAtomic::TVec {
+                known_items,
+                type_param,
+                non_empty,
+                ..
+            } => TAtomic::TDict {
+                known_items: known_items.as_ref().map(|known_items| {
+                    known_items
+                        .iter()
+                        .map(|(offset, (possibly_undefined, _))| {
+                            (
+                                DictKey::Int(*offset as u64),
+                                (*possibly_undefined, Arc::new(mapped_value_type.clone())),
+                            )
+                        })
+                        .collect()
+                }),
+                params: if type_param.is_nothing() {
+                    None
+                } else {
+                    Some((Box::new(get_int()), Box::new(mapped_value_type.clone())))
+                },
+                non_empty: *non_empty,
+                shape_name: None,
+            },
+            _ => {
+                let (key_param, _) = get_arrayish_params(atomic, codebase)?;
+
+                TAtomic::TDict {
+                    known_items: None,
+                    params: Some((Box::new(key_param), Box::new(mapped_value_type.clone()))),
+                    non_empty: false,
+                    shape_name: None,
+                }
+            }
+        });
+    }
+
+    Some(TUnion::new(new_types))
+}
+
+/// Computes the exact chunks of a vec literal, returning None if any of its entries is
+/// possibly undefined or it may contain entries we don't know about. Dicts aren't folded
+/// because their known items don't record insertion order.
+fn get_literal_array_chunks(
+    array_atomic: &TAtomic,
+    chunk_size: usize,
+    preserve_keys: bool,
+) -> Option<TUnion> {
+    let entries = match array_atomic {
+        TAtomic::TVec {
+            known_items: Some(known_items),
+            type_param,
+            ..
+        } if type_param.is_nothing() => known_items
+            .iter()
+            .map(|(offset, (possibly_undefined, item_type))| {
+                (
+                    DictKey::Int(*offset as u64),
+                    *possibly_undefined,
+                    item_type.clone(),
+                )
+            })
+            .collect::<Vec<_>>(),
+        _ => return None,
+    };
+
+    if entries
+        .iter()
+        .any(|(_, possibly_undefined, _)| *possibly_undefined)
+    {
+        return None;
+    }
+
+    let chunks = entries
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(offset, chunk)| {
+            let chunk_type = if preserve_keys {
+                TAtomic::TDict {
+                    known_items: Some(
+                        chunk
+                            .iter()
+                            .map(|(key, _, item_type)| {
+                                (key.clone(), (false, Arc::new(item_type.clone())))
+                            })
+                            .collect(),
+                    ),
+                    params: None,
+                    non_empty: true,
+                    shape_name: None,
+                }
+            } else {
+                TAtomic::TVec {
+                    known_items: Some(
+                        chunk
+                            .iter()
+                            .enumerate()
+                            .map(|(i, (_, _, item_type))| (i, (false, item_type.clone())))
+                            .collect(),
+                    ),
+                    type_param: Box::new(get_nothing()),
+                    known_count: Some(chunk.len()),
+                    non_empty: true,
+                }
+            };
+
+            (offset, (false, wrap_atomic(chunk_type)))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let chunk_count = chunks.len();
+
+    Some(wrap_atomic(TAtomic::TVec {
+        known_items: if chunks.is_empty() {
+            None
+        } else {
+            Some(chunks)
+        },
+        type_param: Box::new(get_nothing()),
+        known_count: Some(chunk_count),
+        non_empty: chunk_count > 0,
+    }))
+}
+
+/// For `array_splice(inout $vec, $offset, $length, $replacement)` this returns both the
+/// type of the removed slice and the type `$vec` has afterwards. Only vecs are modelled,
+/// since splicing a dict renumbers some keys but not others.
+pub(crate) fn get_array_splice_types(
+    args: &[(ast_defs::ParamKind, aast::Expr<(), ()>)],
+    analysis_data: &FunctionAnalysisData,
+    codebase: &CodebaseInfo,
+) -> Option<(TUnion, TUnion)> {
+    let vec_type = analysis_data.get_expr_type(args.first()?.1.pos())?;
+
+    if !vec_type
+        .types
+        .iter()
+        .all(|atomic| matches!(atomic, TAtomic::TVec { .. }))
+    {
+        return None;
+    }
+
+    let offset = args
+        .get(1)
+        .and_then(|(_, arg_expr)| analysis_data.get_expr_type(arg_expr.pos()))
+        .and_then(|offset_type| offset_type.get_single_literal_int_value());
+
+    // None means the length couldn't be determined, Some(None) that it was omitted
+    let length = match args.get(2) {
+        Some((_, arg_expr)) => {
+            let length_type = analysis_data.get_expr_type(arg_expr.pos())?;
+
+            if length_type.is_null() {
+                Some(None)
+            } else {
+                length_type.get_single_literal_int_value().map(Some)
+            }
+        }
+        None => Some(None),
+    };
+
+    let replacement_type = match args.get(3) {
+        Some((_, arg_expr)) => {
+            let replacement_type = analysis_data.get_expr_type(arg_expr.pos())?;
+
+            if replacement_type.is_null() {
+                None
+            } else {
+                Some(replacement_type)
+            }
+        }
+        None => None,
+    };
+
+    if let (Some(offset), Some(length), true) = (offset, length, vec_type.is_single()) {
+        if let Some(splice_types) =
+            get_literal_array_splice_types(vec_type.get_single(), offset, length, replacement_type)
+        {
+            return Some(splice_types);
+        }
+    }
+
+    let mut value_type = get_nothing();
+
+    for atomic in &vec_type.types {
+        if let Some((_, atomic_value_type)) = get_arrayish_params(atomic, codebase) {
+            value_type = add_union_type(value_type, &atomic_value_type, codebase, false);
+        }
+    }
+
+    let mut spliced_value_type = value_type.clone();
+
+    if let Some(replacement_type) = replacement_type {
+        for atomic in &replacement_type.types {
+            // non-array replacements are spliced in as a single element
+            let replacement_value_type =
+                if let TAtomic::TVec { .. } | TAtomic::TDict { .. } = atomic {
+                    get_arrayish_params(atomic, codebase)?.1
+                } else {
+                    wrap_atomic(atomic.clone())
+                };
+
+            spliced_value_type =
+                add_union_type(spliced_value_type, &replacement_value_type, codebase, false);
+        }
+    }
+
+    Some((get_vec(value_type), get_vec(spliced_value_type)))
+}
+
+fn get_literal_array_splice_types(
+    vec_atomic: &TAtomic,
+    offset: i64,
+    length: Option<i64>,
+    replacement_type: Option<&TUnion>,
+) -> Option<(TUnion, TUnion)> {
+    let items = get_defined_vec_items(vec_atomic)?;
+
+    let replacement_items = if let Some(replacement_type) = replacement_type {
+        if !replacement_type.is_single() {
+            return None;
+        }
+
+        match replacement_type.get_single() {
+            replacement_atomic @ TAtomic::TVec { .. } => get_defined_vec_items(replacement_atomic)?,
+            TAtomic::TDict { .. } => return None,
+            replacement_atomic => vec![wrap_atomic(replacement_atomic.clone())],
+        }
+    } else {
+        vec![]
+    };
+
+    let count = items.len() as i64;
+
+    // negative offsets and lengths count back from the end of the vec
+    let start = if offset < 0 {
+        (count + offset).max(0)
+    } else {
+        offset.min(count)
+    };
+
+    let end = match length {
+        None => count,
+        Some(length) if length < 0 => (count + length).max(start),
+        Some(length) => (start + length).min(count),
+    };
+
+    let (start, end) = (start as usize, end as usize);
+
+    let removed_items = items[start..end].to_vec();
+
+    let mut spliced_items = items[..start].to_vec();
+    spliced_items.extend(replacement_items);
+    spliced_items.extend(items[end..].iter().cloned());
+
+    Some((
+        get_vec_from_items(removed_items),
+        get_vec_from_items(spliced_items),
+    ))
+}
+
+fn get_defined_vec_items(vec_atomic: &TAtomic) -> Option<Vec<TUnion>> {
+    match vec_atomic {
+        TAtomic::TVec {
+            known_items: Some(known_items),
+            type_param,
+            ..
+        } if type_param.is_nothing() => known_items
+            .values()
+            .map(|(possibly_undefined, item_type)| {
+                if *possibly_undefined {
+                    None
+                } else {
+                    Some(item_type.clone())
+                }
+            })
+            .collect(),
+        TAtomic::TVec {
+            known_items: None,
+            type_param,
+            ..
+        } if type_param.is_nothing() => Some(vec![]),
+        _ => None,
+    }
+}
+
+/// Returns the values of a vec or dict whose items are all known and defined
+fn get_defined_arrayish_values(atomic: &TAtomic) -> Option<Vec<TUnion>> {
+    match atomic {
+        TAtomic::TVec { .. } => get_defined_vec_items(atomic),
+        TAtomic::TDict {
+            known_items,
+            params: None,
+            ..
+        } => known_items
+            .iter()
+            .flatten()
+            .map(|(_, (possibly_undefined, item_type))| {
+                if *possibly_undefined {
+                    None
+                } else {
+                    Some((**item_type).clone())
+                }
+            })
+            .collect(),
+        _ => None,
+    }
+}
 
-    for c in simple_string.iter().copied() {
-        if in_format_string {
-            in_format_string = false;
-            continue;
+fn get_literal_value_counts(values: Vec<TUnion>) -> Option<BTreeMap<DictKey, i64>> {
+    let mut counts = BTreeMap::new();
+
+    for value in values {
+        let key = if let Some(value) = value.get_single_literal_int_value() {
+            DictKey::Int(value.try_into().ok()?)
+        } else {
+            DictKey::String(value.get_single_literal_string_value()?)
+        };
+
+        *counts.entry(key).or_insert(0) += 1;
+    }
+
+    Some(counts)
+}
+
+fn get_vec_from_items(items: Vec<TUnion>) -> TUnion {
+    wrap_atomic(if items.is_empty() {
+        TAtomic::TVec {
+            known_items: None,
+            type_param: Box::new(get_nothing()),
+            known_count: Some(0),
+            non_empty: false,
         }
+    } else {
+        TAtomic::TVec {
+            known_count: Some(items.len()),
+            known_items: Some(
+                items
+                    .into_iter()
+                    .map(|item| (false, item))
+                    .enumerate()
+                    .collect(),
+            ),
+            type_param: Box::new(get_nothing()),
+            non_empty: true,
+        }
+    })
+}
 
-        if !escaped {
-            if c as char == '%' {
-                in_format_string = true;
-                literals.push(aast::Expr(
-                    (),
-                    first_arg.1.pos().clone(),
-                    aast::Expr_::String(BString::from(cur_literal)),
-                ));
-                cur_literal = "".to_string();
-                continue;
+const MAX_RANGE_KNOWN_ITEMS: u64 = 16;
+
+/// Both `range` and `Vec\range` count down when `$start > $end`, so the step
+/// only needs to be positive. Non-positive steps throw, and are reported elsewhere.
+fn get_range_vec_type(start: i64, end: i64, step: i64) -> Option<TUnion> {
+    if step <= 0 {
+        return None;
+    }
+
+    let count = start.abs_diff(end) / (step as u64) + 1;
+
+    if count > MAX_RANGE_KNOWN_ITEMS {
+        return Some(wrap_atomic(TAtomic::TVec {
+            known_items: None,
+            type_param: Box::new(get_int()),
+            known_count: Some(count as usize),
+            non_empty: true,
+        }));
+    }
+
+    let step = if start <= end { step } else { -step };
+
+    Some(wrap_atomic(TAtomic::TVec {
+        known_items: Some(
+            (0..count as usize)
+                .map(|i| (i, (false, get_literal_int(start + (i as i64) * step))))
+                .collect(),
+        ),
+        type_param: Box::new(get_nothing()),
+        known_count: Some(count as usize),
+        non_empty: true,
+    }))
+}
+
+/// Sorting keeps the element type and the number of elements, but not their order.
+pub(crate) fn get_sorted_vec_type(
+    collection_type: &TUnion,
+    codebase: &CodebaseInfo,
+) -> Option<TUnion> {
+    let mut new_types = vec![];
+
+    for atomic in &collection_type.types {
+        let (known_count, non_empty) = match atomic {
+            TAtomic::TVec {
+                known_items: Some(known_items),
+                type_param,
+                known_count,
+                non_empty,
+            } => {
+                let has_possibly_undefined_items = known_items
+                    .values()
+                    .any(|(possibly_undefined, _)| *possibly_undefined);
+
+                (
+                    if type_param.is_nothing() && !has_possibly_undefined_items {
+                        Some(known_items.len())
+                    } else {
+                        *known_count
+                    },
+                    *non_empty
+                        || known_items
+                            .values()
+                            .any(|(possibly_undefined, _)| !possibly_undefined),
+                )
             }
+            TAtomic::TVec {
+                known_count,
+                non_empty,
+                ..
+            } => (*known_count, *non_empty),
+            TAtomic::TDict {
+                known_items,
+                non_empty,
+                ..
+            } => (
+                None,
+                *non_empty
+                    || known_items.as_ref().map_or(false, |known_items| {
+                        known_items
+                            .values()
+                            .any(|(possibly_undefined, _)| !possibly_undefined)
+                    }),
+            ),
+            _ => (None, false),
+        };
 
-            if c as char == '\\' {
-                escaped = true;
+        let (_, value_type) = get_arrayish_params(atomic, codebase)?;
+
+        new_types.push(TAtomic::TVec {
+            known_items: None,
+            type_param: Box::new(value_type),
+            known_count,
+            non_empty,
+        });
+    }
+
+    Some(TUnion::new(new_types))
+}
+
+/// Swaps the keys and values of a vec or dict. Known items whose values are all
+/// literal keys stay known, so looking up a value that wasn't in the original
+/// collection is flagged as a possibly-undefined offset.
+fn get_flipped_array_type(atomic: &TAtomic, codebase: &CodebaseInfo) -> Option<TAtomic> {
+    let known_entries = match atomic {
+        TAtomic::TDict {
+            known_items: Some(known_items),
+            params: None,
+            ..
+        } => Some(
+            known_items
+                .iter()
+                .map(|(key, (possibly_undefined, item_type))| {
+                    (key.clone(), *possibly_undefined, (**item_type).clone())
+                })
+                .collect::<Vec<_>>(),
+        ),
+        TAtomic::TVec {
+            known_items: Some(known_items),
+            type_param,
+            ..
+        } if type_param.is_nothing() => Some(
+            known_items
+                .iter()
+                .map(|(offset, (possibly_undefined, item_type))| {
+                    (
+                        DictKey::Int(*offset as u64),
+                        *possibly_undefined,
+                        item_type.clone(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ),
+        _ => None,
+    };
+
+    if let Some(known_entries) = known_entries {
+        if let Some(flipped_items) = get_flipped_known_items(known_entries, codebase) {
+            let non_empty = flipped_items
+                .values()
+                .any(|(possibly_undefined, _)| !possibly_undefined);
+
+            return Some(TAtomic::TDict {
+                known_items: if flipped_items.is_empty() {
+                    None
+                } else {
+                    Some(flipped_items)
+                },
+                params: None,
+                non_empty,
+                shape_name: None,
+            });
+        }
+    }
+
+    let (key_param, value_param) = get_arrayish_params(atomic, codebase)?;
+
+    // only arraykey values can become keys
+    if !value_param.types.iter().all(|value_atomic| {
+        value_atomic.is_int()
+            || value_atomic.is_string()
+            || matches!(
+                value_atomic,
+                TAtomic::TArraykey { .. }
+                    | TAtomic::TEnum { .. }
+                    | TAtomic::TEnumLiteralCase { .. }
+            )
+    }) {
+        return None;
+    }
+
+    Some(TAtomic::TDict {
+        known_items: None,
+        params: Some((Box::new(value_param), Box::new(key_param))),
+        non_empty: false,
+        shape_name: None,
+    })
+}
+
+fn get_flipped_known_items(
+    known_entries: Vec<(DictKey, bool, TUnion)>,
+    codebase: &CodebaseInfo,
+) -> Option<BTreeMap<DictKey, (bool, Arc<TUnion>)>> {
+    let mut flipped_items: BTreeMap<DictKey, (bool, Arc<TUnion>)> = BTreeMap::new();
+
+    for (key, possibly_undefined, item_type) in known_entries {
+        let flipped_key = match item_type.get_single_dict_key()? {
+            // get_single_dict_key casts negative ints, which we can't represent
+            DictKey::Int(_) if item_type.get_single_literal_int_value()? < 0 => {
+                return None;
             }
+            flipped_key => flipped_key,
+        };
 
-            in_format_string = false;
+        let key_type = match &key {
+            DictKey::Int(i) => get_literal_int(*i as i64),
+            DictKey::String(k) => get_literal_string(k.clone()),
+            DictKey::Enum(c, m) => {
+                codebase.get_class_constant_type(c, false, m, FxHashSet::default())?
+            }
+        };
+
+        // duplicate values collapse into a single key that could hold any of
+        // the original keys, and is only undefined if all of them are
+        let flipped_item = if let Some((existing_possibly_undefined, existing_type)) =
+            flipped_items.get(&flipped_key)
+        {
+            (
+                *existing_possibly_undefined && possibly_undefined,
+                Arc::new(add_union_type(
+                    (**existing_type).clone(),
+                    &key_type,
+                    codebase,
+                    false,
+                )),
+            )
         } else {
-            if c as char == '\\' {
-                cur_literal += "\\";
-                escaped = false;
-                continue;
+            (possibly_undefined, Arc::new(key_type))
+        };
+
+        flipped_items.insert(flipped_key, flipped_item);
+    }
+
+    Some(flipped_items)
+}
+
+/// Only a vec of literal keys is precise enough to compute the selected shape.
+fn get_selected_dict_keys(keys_type: &TUnion) -> Option<Vec<DictKey>> {
+    if !keys_type.is_single() {
+        return None;
+    }
+
+    get_defined_vec_items(keys_type.get_single())?
+        .into_iter()
+        .map(|key_type| {
+            if key_type
+                .get_single_literal_int_value()
+                .map_or(false, |i| i < 0)
+            {
+                None
+            } else {
+                key_type.get_single_dict_key()
             }
+        })
+        .collect()
+}
 
-            escaped = false;
+fn get_dict_with_selected_keys(atomic: &TAtomic, selected_keys: &[DictKey]) -> Option<TAtomic> {
+    let (known_items, params) = if let TAtomic::TDict {
+        known_items,
+        params,
+        ..
+    } = atomic
+    {
+        (known_items, params)
+    } else {
+        return None;
+    };
+
+    let mut selected_items = BTreeMap::new();
+
+    // keys that can't be in a sealed shape are never selected
+    for key in selected_keys {
+        if let Some(known_item) = known_items.as_ref().and_then(|items| items.get(key)) {
+            selected_items.insert(key.clone(), known_item.clone());
+        } else if let Some((_, value_param)) = params {
+            // the key may or may not be among the dict's unknown keys
+            selected_items.insert(key.clone(), (true, Arc::new((**value_param).clone())));
         }
+    }
 
-        cur_literal += (c as char).to_string().as_str();
+    Some(get_dict_from_known_items(selected_items, None))
+}
+
+/// Returns the keys a `Dict\diff_by_key` argument definitely and possibly removes,
+/// or None for the possibly-removed keys when any key could be removed.
+fn get_removed_dict_keys(removed_type: &TUnion) -> Option<(Vec<DictKey>, Option<Vec<DictKey>>)> {
+    let mut definitely_removed = None::<Vec<DictKey>>;
+    let mut possibly_removed = Some(vec![]);
+
+    for atomic in &removed_type.types {
+        let (known_keys, has_unknown_keys) = match atomic {
+            TAtomic::TDict {
+                known_items,
+                params,
+                ..
+            } => (
+                known_items
+                    .iter()
+                    .flatten()
+                    .map(|(key, (possibly_undefined, _))| (key.clone(), *possibly_undefined))
+                    .collect::<Vec<_>>(),
+                params.is_some(),
+            ),
+            TAtomic::TVec {
+                known_items,
+                type_param,
+                ..
+            } => (
+                known_items
+                    .iter()
+                    .flatten()
+                    .map(|(offset, (possibly_undefined, _))| {
+                        (DictKey::Int(*offset as u64), *possibly_undefined)
+                    })
+                    .collect::<Vec<_>>(),
+                !type_param.is_nothing(),
+            ),
+            TAtomic::TNothing => continue,
+            _ => (vec![], true),
+        };
+
+        let defined_keys = known_keys
+            .iter()
+            .filter(|(_, possibly_undefined)| !possibly_undefined)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        definitely_removed = Some(if let Some(definitely_removed) = definitely_removed {
+            definitely_removed
+                .into_iter()
+                .filter(|key| defined_keys.contains(key))
+                .collect()
+        } else {
+            defined_keys
+        });
+
+        if has_unknown_keys {
+            possibly_removed = None;
+        } else if let Some(possibly_removed) = &mut possibly_removed {
+            possibly_removed.extend(known_keys.into_iter().map(|(key, _)| key));
+        }
     }
 
-    literals.push(aast::Expr(
-        (),
-        first_arg.1.pos().clone(),
-        aast::Expr_::String(BString::from(cur_literal)),
-    ));
+    Some((definitely_removed.unwrap_or_default(), possibly_removed))
+}
 
-    let mut concat_args = vec![];
+fn get_dict_without_keys(
+    atomic: &TAtomic,
+    removed_keys: &[(Vec<DictKey>, Option<Vec<DictKey>>)],
+) -> Option<TAtomic> {
+    let (known_items, params) = if let TAtomic::TDict {
+        known_items,
+        params,
+        ..
+    } = atomic
+    {
+        (known_items, params)
+    } else {
+        return None;
+    };
 
-    for (i, literal) in literals.iter().enumerate() {
-        concat_args.push(literal);
-        if let Some(arg) = args.get(i + 1) {
-            concat_args.push(&arg.1);
+    let mut remaining_items = known_items.clone().unwrap_or_default();
+
+    for (definitely_removed, possibly_removed) in removed_keys {
+        for key in definitely_removed {
+            remaining_items.remove(key);
+        }
+
+        for (key, (possibly_undefined, _)) in remaining_items.iter_mut() {
+            if possibly_removed
+                .as_ref()
+                .map_or(true, |possibly_removed| possibly_removed.contains(key))
+            {
+                *possibly_undefined = true;
+            }
+        }
+    }
+
+    Some(get_dict_from_known_items(remaining_items, params.clone()))
+}
+
+/// Removes null from the element type of a `filter_nulls` result, turning mixed into nonnull
+fn remove_null_from_element_type(element_type: TUnion) -> TUnion {
+    let types = element_type
+        .types
+        .into_iter()
+        .filter(|atomic| !matches!(atomic, TAtomic::TNull))
+        .map(|atomic| match atomic {
+            TAtomic::TMixed => TAtomic::TMixedWithFlags(false, false, false, true),
+            TAtomic::TMixedWithFlags(is_any, is_truthy, is_falsy, _) => {
+                TAtomic::TMixedWithFlags(is_any, is_truthy, is_falsy, true)
+            }
+            atomic => atomic,
+        })
+        .collect::<Vec<_>>();
+
+    if types.is_empty() {
+        get_nothing()
+    } else {
+        TUnion::new(types)
+    }
+}
+
+/// Dict known items aren't ordered, so reversing one only matters when int keys
+/// are renumbered. Vecs need all their items to be defined to be reversed precisely.
+fn get_reversed_type(container_atomic: &TAtomic, preserve_keys: bool) -> Option<TUnion> {
+    match container_atomic {
+        TAtomic::TVec { .. } => {
+            let mut items = get_defined_vec_items(container_atomic)?;
+
+            if preserve_keys && !items.is_empty() {
+                return Some(wrap_atomic(get_dict_from_known_items(
+                    items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(offset, item)| {
+                            (DictKey::Int(offset as u64), (false, Arc::new(item)))
+                        })
+                        .collect(),
+                    None,
+                )));
+            }
+
+            items.reverse();
+
+            Some(get_vec_from_items(items))
+        }
+        TAtomic::TDict {
+            known_items,
+            params,
+            ..
+        } => {
+            let has_only_string_keys = known_items
+                .iter()
+                .flatten()
+                .all(|(key, _)| matches!(key, DictKey::String(_)))
+                && params.as_ref().map_or(true, |(key_param, _)| {
+                    key_param.types.iter().all(|atomic| atomic.is_string())
+                });
+
+            if preserve_keys || has_only_string_keys {
+                Some(wrap_atomic(container_atomic.clone()))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Splits a vec of literal values into the keysets `Keyset\chunk` would build.
+/// Dicts aren't handled since their known items don't record insertion order.
+fn get_keyset_chunks(traversable_atomic: &TAtomic, chunk_size: usize) -> Option<TUnion> {
+    let mut chunks = vec![];
+    let mut current_chunk = vec![];
+    let mut current_chunk_count = 0;
+
+    for item_type in get_defined_vec_items(traversable_atomic)? {
+        if !item_type.is_single() {
+            return None;
+        }
+
+        let value = match item_type.get_single() {
+            value @ (TAtomic::TLiteralInt { .. } | TAtomic::TLiteralString { .. }) => value.clone(),
+            _ => return None,
+        };
+
+        // every element counts towards the chunk size, even when it's already in the chunk
+        if !current_chunk.contains(&value) {
+            current_chunk.push(value);
+        }
+
+        current_chunk_count += 1;
+
+        if current_chunk_count == chunk_size {
+            chunks.push(std::mem::take(&mut current_chunk));
+            current_chunk_count = 0;
+        }
+    }
+
+    if !current_chunk.is_empty() {
+        chunks.push(current_chunk);
+    }
+
+    Some(get_vec_from_items(
+        chunks
+            .into_iter()
+            .map(|chunk| {
+                wrap_atomic(TAtomic::TKeyset {
+                    type_param: Box::new(TUnion::new(chunk)),
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Merges the dicts of a `Dict\flatten` argument in order, so later keys overwrite
+/// earlier ones. Only vecs of known length are handled.
+fn get_flattened_dict(traversable_atomic: &TAtomic, codebase: &CodebaseInfo) -> Option<TAtomic> {
+    let elements = match traversable_atomic {
+        TAtomic::TVec {
+            known_items: Some(known_items),
+            type_param,
+            ..
+        } if type_param.is_nothing() => known_items,
+        _ => return None,
+    };
+
+    let mut flattened_items = BTreeMap::<DictKey, (bool, Arc<TUnion>)>::new();
+    let mut flattened_params: Option<(Box<TUnion>, Box<TUnion>)> = None;
+
+    for (element_possibly_undefined, element_type) in elements.values() {
+        if element_type.types.len() != 1 {
+            return None;
+        }
+
+        let (known_items, params) = if let TAtomic::TDict {
+            known_items,
+            params,
+            ..
+        } = &element_type.types[0]
+        {
+            (known_items, params)
         } else {
-            break;
+            return None;
+        };
+
+        if let Some((key_param, value_param)) = params {
+            // any existing key may be overwritten by one of the unknown keys
+            for (_, existing_type) in flattened_items.values_mut() {
+                *existing_type = Arc::new(add_union_type(
+                    (**existing_type).clone(),
+                    value_param,
+                    codebase,
+                    false,
+                ));
+            }
+
+            flattened_params = Some(
+                if let Some((existing_key, existing_value)) = flattened_params {
+                    (
+                        Box::new(add_union_type(*existing_key, key_param, codebase, false)),
+                        Box::new(add_union_type(
+                            *existing_value,
+                            value_param,
+                            codebase,
+                            false,
+                        )),
+                    )
+                } else {
+                    (key_param.clone(), value_param.clone())
+                },
+            );
+        }
+
+        for (key, (possibly_undefined, item_type)) in known_items.iter().flatten() {
+            if !possibly_undefined && !element_possibly_undefined {
+                flattened_items.insert(key.clone(), (false, item_type.clone()));
+            } else if let Some((_, existing_type)) = flattened_items.get_mut(key) {
+                *existing_type = Arc::new(add_union_type(
+                    (**existing_type).clone(),
+                    item_type,
+                    codebase,
+                    false,
+                ));
+            } else if let Some((_, value_param)) = &flattened_params {
+                // the key may already be among the unknown keys
+                flattened_items.insert(
+                    key.clone(),
+                    (
+                        true,
+                        Arc::new(add_union_type(
+                            (**item_type).clone(),
+                            value_param,
+                            codebase,
+                            false,
+                        )),
+                    ),
+                );
+            } else {
+                flattened_items.insert(key.clone(), (true, item_type.clone()));
+            }
         }
     }
 
-    analyze_concat_nodes(concat_args, statements_analyzer, analysis_data, pos)
+    Some(get_dict_from_known_items(flattened_items, flattened_params))
+}
+
+// Known items are stored by key rather than in insertion order, so unless the
+// count covers the whole dict we can't tell which of them are kept
+fn get_dict_window(dict_atomic: &TAtomic, count: Option<i64>, is_take: bool) -> Option<TAtomic> {
+    let (known_items, params) = if let TAtomic::TDict {
+        known_items,
+        params,
+        ..
+    } = dict_atomic
+    {
+        (known_items, params)
+    } else {
+        return None;
+    };
+
+    let known_count = if params.is_none()
+        && known_items
+            .iter()
+            .flatten()
+            .all(|(_, (possibly_undefined, _))| !possibly_undefined)
+    {
+        Some(
+            known_items
+                .as_ref()
+                .map_or(0, |known_items| known_items.len()) as i64,
+        )
+    } else {
+        None
+    };
+
+    let empty_dict = get_dict_from_known_items(BTreeMap::new(), None);
+
+    Some(match (count, known_count) {
+        (Some(0), _) => {
+            if is_take {
+                empty_dict
+            } else {
+                dict_atomic.clone()
+            }
+        }
+        (Some(count), Some(known_count)) if count >= known_count => {
+            if is_take {
+                dict_atomic.clone()
+            } else {
+                empty_dict
+            }
+        }
+        _ => get_dict_from_known_items(
+            known_items
+                .iter()
+                .flatten()
+                .map(|(key, (_, item_type))| (key.clone(), (true, item_type.clone())))
+                .collect(),
+            params.clone(),
+        ),
+    })
+}
+
+fn get_dict_from_known_items(
+    known_items: BTreeMap<DictKey, (bool, Arc<TUnion>)>,
+    params: Option<(Box<TUnion>, Box<TUnion>)>,
+) -> TAtomic {
+    let non_empty = known_items
+        .values()
+        .any(|(possibly_undefined, _)| !possibly_undefined);
+
+    TAtomic::TDict {
+        known_items: if known_items.is_empty() {
+            None
+        } else {
+            Some(known_items)
+        },
+        params,
+        non_empty,
+        shape_name: None,
+    }
+}
+
+/// Mirrors json_decode with `$assoc = true`, where both objects and lists become
+/// dicts, and ints that don't fit in 64 bits become floats.
+fn get_decoded_json_type(value: &serde_json::Value) -> Option<TUnion> {
+    Some(match value {
+        serde_json::Value::Null => get_null(),
+        serde_json::Value::Bool(true) => get_true(),
+        serde_json::Value::Bool(false) => get_false(),
+        serde_json::Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                get_literal_int(value)
+            } else {
+                get_float()
+            }
+        }
+        serde_json::Value::String(value) => get_literal_string(value.clone()),
+        serde_json::Value::Array(items) => {
+            let mut known_items = BTreeMap::new();
+
+            for (offset, item) in items.iter().enumerate() {
+                known_items.insert(
+                    DictKey::Int(offset as u64),
+                    (false, Arc::new(get_decoded_json_type(item)?)),
+                );
+            }
+
+            wrap_atomic(get_dict_from_known_items(known_items, None))
+        }
+        serde_json::Value::Object(entries) => {
+            let mut known_items = BTreeMap::new();
+
+            for (key, item) in entries {
+                // integer-like keys are converted to ints, as with any PHP array
+                let dict_key = match key.parse::<i64>() {
+                    Ok(int_key) if int_key.to_string() == *key => {
+                        if int_key < 0 {
+                            return None;
+                        }
+
+                        DictKey::Int(int_key as u64)
+                    }
+                    _ => DictKey::String(key.clone()),
+                };
+
+                known_items.insert(dict_key, (false, Arc::new(get_decoded_json_type(item)?)));
+            }
+
+            wrap_atomic(get_dict_from_known_items(known_items, None))
+        }
+    })
+}
+
+fn get_awaited_type(awaitable_type: &TUnion, codebase: &CodebaseInfo) -> Option<TUnion> {
+    let mut awaited_type = None;
+
+    for atomic in &awaitable_type.types {
+        if let TAtomic::TAwaitable { value } = atomic {
+            awaited_type = Some(add_optional_union_type(
+                (**value).clone(),
+                awaited_type.as_ref(),
+                codebase,
+            ));
+        } else {
+            return None;
+        }
+    }
+
+    awaited_type
 }
 
 fn get_type_structure_type(
@@ -803,7 +3709,7 @@ fn add_dataflow(
     let data_flow_graph = &mut analysis_data.data_flow_graph;
 
     if let GraphKind::WholeProgram(_) = &data_flow_graph.kind {
-        if !context.allow_taints {
+        if !context.allow_taints || functionlike_storage.safe_return {
             return stmt_type;
         }
     }
@@ -1058,6 +3964,7 @@ fn get_special_argument_nodes(
             | StrId::ESCAPESHELLARG
             | StrId::FIXME_UNSAFE_CAST
             | StrId::LIB_DICT_COUNT_VALUES
+            | StrId::ARRAY_COUNT_VALUES
             | StrId::LIB_DICT_UNIQUE
             | StrId::LIB_STR_REVERSE
             | StrId::LIB_VEC_CAST_CLEAR_LEGACY_ARRAY_MARK
@@ -1435,7 +4342,7 @@ fn get_special_argument_nodes(
                 ],
                 None,
             ),
-            StrId::LIB_VEC_SORT => (vec![(0, PathKind::Default)], None),
+            StrId::LIB_VEC_SORT | StrId::LIB_VEC_SORT_BY => (vec![(0, PathKind::Default)], None),
             StrId::LIB_STR_JOIN => (
                 vec![
                     (0, PathKind::UnknownArrayFetch(ArrayDataKind::ArrayValue)),
@@ -1464,7 +4371,7 @@ fn get_special_argument_nodes(
                 vec![(0, PathKind::Default)],
                 None,
             ),
-            StrId::LIB_DICT_FLIP => (
+            StrId::LIB_DICT_FLIP | StrId::ARRAY_FLIP => (
                 // todo improve this
                 vec![(0, PathKind::Default)],
                 None,
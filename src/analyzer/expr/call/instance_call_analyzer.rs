@@ -44,6 +44,8 @@ pub(crate) fn analyze(
     if let aast::Expr_::Id(_) = &expr.1 .2 {
         // do nothing
     } else {
+        analysis_data.has_dynamic_calls = true;
+
         expression_analyzer::analyze(
             statements_analyzer,
             expr.1,
@@ -75,17 +77,18 @@ pub(crate) fn analyze(
     if class_type.is_null() || class_type.is_void() {
         if nullsafe {
             has_nullsafe_null = true;
+        } else {
+            analysis_data.maybe_add_issue(
+                Issue::new(
+                    IssueKind::MethodCallOnNull,
+                    "Cannot call method on null value".to_string(),
+                    statements_analyzer.get_hpos(expr.1.pos()),
+                    &context.function_context.calling_functionlike_id,
+                ),
+                statements_analyzer.get_config(),
+                statements_analyzer.get_file_path_actual(),
+            );
         }
-        analysis_data.maybe_add_issue(
-            Issue::new(
-                IssueKind::MethodCallOnNull,
-                "Cannot call method on null value".to_string(),
-                statements_analyzer.get_hpos(expr.1.pos()),
-                &context.function_context.calling_functionlike_id,
-            ),
-            statements_analyzer.get_config(),
-            statements_analyzer.get_file_path_actual(),
-        );
     } else {
         if class_type.is_mixed() {
             for origin in &class_type.parent_nodes {
@@ -181,6 +184,9 @@ pub(crate) fn analyze(
             context.has_returned = true;
         }
         analysis_data.set_expr_type(pos, stmt_type);
+    } else if has_nullsafe_null {
+        // the receiver is always null, so the nullsafe call short-circuits
+        analysis_data.set_expr_type(pos, get_null());
     }
 
     Ok(())
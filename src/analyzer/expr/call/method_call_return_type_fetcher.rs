@@ -248,7 +248,7 @@ fn add_dataflow(
     let data_flow_graph = &mut analysis_data.data_flow_graph;
 
     if let GraphKind::WholeProgram(_) = &data_flow_graph.kind {
-        if !context.allow_taints {
+        if !context.allow_taints || functionlike_storage.safe_return {
             return return_type_candidate;
         }
     }
@@ -433,6 +433,28 @@ fn add_dataflow(
             }
         }
 
+        // fluent methods that return `this` pass the receiver through, so anything that
+        // flowed into the receiver also flows out of the call. This is conservative for
+        // methods that return a fresh instance instead.
+        if let (Some(lhs_expr), Some(return_type)) = (lhs_expr, &functionlike_storage.return_type) {
+            if return_type.is_static_object() {
+                if let Some(lhs_type) = analysis_data.expr_types.get(&(
+                    lhs_expr.pos().start_offset() as u32,
+                    lhs_expr.pos().end_offset() as u32,
+                )) {
+                    for parent_node in &lhs_type.parent_nodes {
+                        data_flow_graph.add_path(
+                            parent_node,
+                            &method_call_node,
+                            PathKind::Default,
+                            vec![],
+                            vec![],
+                        );
+                    }
+                }
+            }
+        }
+
         if !functionlike_storage.taint_source_types.is_empty() {
             let method_call_node_source = DataFlowNode {
                 id: method_call_node.id.clone(),
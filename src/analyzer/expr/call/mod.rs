@@ -5,6 +5,7 @@ pub(crate) mod atomic_static_call_analyzer;
 pub(crate) mod class_template_param_collector;
 pub(crate) mod existing_atomic_method_call_analyzer;
 pub(crate) mod expression_call_analyzer;
+pub(crate) mod format_string_analyzer;
 pub(crate) mod function_call_analyzer;
 pub(crate) mod function_call_return_type_fetcher;
 pub(crate) mod instance_call_analyzer;
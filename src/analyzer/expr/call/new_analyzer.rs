@@ -10,7 +10,9 @@ use hakana_str::StrId;
 use hakana_type::template::standin_type_replacer::get_most_specific_type_from_bounds;
 use rustc_hash::FxHashMap;
 
-use crate::expr::call_analyzer::{check_method_args, get_generic_param_for_offset};
+use crate::expr::call_analyzer::{
+    check_method_args, get_generic_param_for_offset, report_deprecated_usage,
+};
 use crate::expression_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::scope::BlockContext;
@@ -297,8 +299,35 @@ fn analyze_named_constructor(
         return Ok(());
     };
 
-    if from_static {
-        // todo check for unsafe instantiation
+    // new static is always safe in a final class, since there are no child classes
+    if from_static
+        && statements_analyzer.get_config().find_unsafe_instantiations
+        && !storage.is_final
+        && !storage.preserve_constructor_signature
+    {
+        let has_final_constructor = codebase
+            .get_method(
+                &codebase
+                    .get_declaring_method_id(&MethodIdentifier(classlike_name, StrId::CONSTRUCT)),
+            )
+            .and_then(|functionlike_info| functionlike_info.method_info.as_ref())
+            .map_or(false, |method_info| method_info.is_final);
+
+        if !has_final_constructor {
+            analysis_data.maybe_add_issue(
+                Issue::new(
+                    IssueKind::UnsafeInstantiation,
+                    format!(
+                        "Cannot safely instantiate {} with new static, since child classes may change its constructor",
+                        statements_analyzer.get_interner().lookup(&classlike_name)
+                    ),
+                    statements_analyzer.get_hpos(pos),
+                    &context.function_context.calling_functionlike_id,
+                ),
+                statements_analyzer.get_config(),
+                statements_analyzer.get_file_path_actual(),
+            );
+        }
     }
 
     if storage.is_abstract && !can_extend && !from_classname {
@@ -324,7 +353,18 @@ fn analyze_named_constructor(
             true
         }
     {
-        // todo complain about deprecated class
+        report_deprecated_usage(
+            statements_analyzer,
+            analysis_data,
+            context,
+            IssueKind::DeprecatedClass,
+            format!(
+                "Class {}",
+                statements_analyzer.get_interner().lookup(&classlike_name)
+            ),
+            storage.deprecation_message,
+            pos,
+        );
     }
 
     let mut generic_type_params = None;
@@ -4,6 +4,8 @@ use crate::scope_analyzer::ScopeAnalyzer;
 use crate::scope::BlockContext;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt_analyzer::AnalysisError;
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::method_identifier::MethodIdentifier;
 use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_reflection_info::EFFECT_WRITE_PROPS;
 use hakana_str::StrId;
@@ -32,6 +34,10 @@ pub(crate) fn analyze(
 
     let mut classlike_name = None;
 
+    // the class a method is resolved against when the call isn't late-static-bound,
+    // used to flag calls to abstract methods
+    let mut exact_classlike_name = None;
+
     let resolved_names = statements_analyzer.get_file_analyzer().resolved_names;
 
     let lhs_type = match &expr.0 .2 {
@@ -54,6 +60,8 @@ pub(crate) fn analyze(
                                 return Err(AnalysisError::UserError);
                             };
 
+                        // like static::, self:: forwards the called class, so it can
+                        // reach a concrete override of an abstract method
                         classlike_name = Some(*self_name);
 
                         get_named_object(*self_name, None)
@@ -77,6 +85,7 @@ pub(crate) fn analyze(
                             };
 
                         classlike_name = Some(parent_name);
+                        exact_classlike_name = Some(parent_name);
 
                         wrap_atomic(TAtomic::TNamedObject {
                             name: *self_name,
@@ -115,6 +124,7 @@ pub(crate) fn analyze(
                         match lhs.get_single() {
                             TAtomic::TNamedObject { name, .. } => {
                                 classlike_name = Some(*name);
+                                exact_classlike_name = Some(*name);
                             }
                             TAtomic::TGenericClassname { as_type, .. } => {
                                 if let TAtomic::TNamedObject { name, .. } = &**as_type {
@@ -149,6 +159,17 @@ pub(crate) fn analyze(
         }
     };
 
+    if let Some(exact_classlike_name) = exact_classlike_name {
+        check_abstract_method_call(
+            statements_analyzer,
+            exact_classlike_name,
+            &expr.1 .1,
+            pos,
+            analysis_data,
+            context,
+        );
+    }
+
     let mut result = AtomicMethodCallAnalysisResult::new();
 
     for lhs_type_part in &lhs_type.types {
@@ -178,3 +199,53 @@ pub(crate) fn analyze(
 
     Ok(())
 }
+
+// new static in a class whose children may change its constructor is reported by
+// new_analyzer as UnsafeInstantiation, so this only covers calls to abstract methods
+fn check_abstract_method_call(
+    statements_analyzer: &StatementsAnalyzer,
+    classlike_name: StrId,
+    method_name: &str,
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+) {
+    let codebase = statements_analyzer.get_codebase();
+    let interner = statements_analyzer.get_interner();
+
+    let method_name = if let Some(method_name) = interner.get(method_name) {
+        method_name
+    } else {
+        return;
+    };
+
+    let declaring_method_id =
+        codebase.get_declaring_method_id(&MethodIdentifier(classlike_name, method_name));
+
+    // abstract trait methods are implemented by whichever class uses the trait
+    if codebase.trait_exists(&declaring_method_id.0) {
+        return;
+    }
+
+    let is_abstract = codebase
+        .get_method(&declaring_method_id)
+        .and_then(|functionlike_info| functionlike_info.method_info.as_ref())
+        .map_or(false, |method_info| method_info.is_abstract);
+
+    if is_abstract {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::AbstractMethodCall,
+                format!(
+                    "Cannot call abstract method {}::{}",
+                    interner.lookup(&declaring_method_id.0),
+                    interner.lookup(&declaring_method_id.1)
+                ),
+                statements_analyzer.get_hpos(pos),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
+}
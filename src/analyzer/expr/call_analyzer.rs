@@ -435,9 +435,64 @@ pub(crate) fn check_method_args(
         )
     }
 
+    if functionlike_storage.deprecated {
+        report_deprecated_usage(
+            statements_analyzer,
+            analysis_data,
+            context,
+            IssueKind::DeprecatedMethod,
+            format!(
+                "Method {}",
+                method_id.to_string(statements_analyzer.get_interner())
+            ),
+            functionlike_storage.deprecation_message,
+            pos,
+        );
+    }
+
     Ok(())
 }
 
+/// Uses of deprecated symbols from inside deprecated code aren't reported, since
+/// they'll be removed together.
+pub(crate) fn report_deprecated_usage(
+    statements_analyzer: &StatementsAnalyzer,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+    issue_kind: IssueKind,
+    symbol_description: String,
+    deprecation_message: Option<StrId>,
+    pos: &Pos,
+) {
+    if context
+        .function_context
+        .is_deprecated(statements_analyzer.get_codebase())
+    {
+        return;
+    }
+
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            issue_kind,
+            if let Some(deprecation_message) = deprecation_message {
+                format!(
+                    "{} is deprecated: {}",
+                    symbol_description,
+                    statements_analyzer
+                        .get_interner()
+                        .lookup(&deprecation_message)
+                )
+            } else {
+                format!("{} is deprecated", symbol_description)
+            },
+            statements_analyzer.get_hpos(pos),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
+
 pub(crate) fn apply_effects(
     functionlike_id: FunctionLikeIdentifier,
     function_storage: &FunctionLikeInfo,
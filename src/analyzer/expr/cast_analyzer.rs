@@ -3,14 +3,20 @@ use std::rc::Rc;
 use rustc_hash::FxHashMap;
 
 use crate::scope::BlockContext;
+use crate::scope_analyzer::ScopeAnalyzer;
 use crate::statements_analyzer::StatementsAnalyzer;
 
 use crate::expression_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::stmt_analyzer::AnalysisError;
+use hakana_reflection_info::codebase_info::CodebaseInfo;
 use hakana_reflection_info::data_flow::graph::GraphKind;
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_reflector::typehint_resolver::get_type_from_hint;
 use hakana_type::get_mixed_any;
+use hakana_type::type_comparator::type_comparison_result::TypeComparisonResult;
+use hakana_type::type_comparator::union_type_comparator;
 use oxidized::aast;
 
 pub(crate) fn analyze(
@@ -45,7 +51,38 @@ pub(crate) fn analyze(
     )
     .unwrap();
 
-    // todo emit issues about redundant casts
+    if statements_analyzer.get_config().find_redundant_casts
+        && !expr_type.is_mixed()
+        && !expr_type.is_nothing()
+        && !expr_type
+            .types
+            .iter()
+            .any(|t| needs_explicit_cast(statements_analyzer.get_codebase(), t))
+        && union_type_comparator::is_contained_by(
+            statements_analyzer.get_codebase(),
+            &expr_type,
+            &hint_type,
+            false,
+            false,
+            false,
+            &mut TypeComparisonResult::new(),
+        )
+    {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::RedundantCast,
+                format!(
+                    "Redundant cast of {} to {}",
+                    expr_type.get_id(Some(statements_analyzer.get_interner())),
+                    hint_type.get_id(Some(statements_analyzer.get_interner())),
+                ),
+                statements_analyzer.get_hpos(expr_pos),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
 
     if hint_type.has_taintable_value()
         || analysis_data.data_flow_graph.kind == GraphKind::FunctionBody
@@ -68,3 +105,22 @@ pub(crate) fn analyze(
 
     Ok(())
 }
+
+// The comparator treats these types as their underlying or bound type, but
+// Hack's typechecker still requires an explicit cast to get at that type.
+fn needs_explicit_cast(codebase: &CodebaseInfo, atomic: &TAtomic) -> bool {
+    match atomic {
+        TAtomic::TEnum { name, .. }
+        | TAtomic::TEnumLiteralCase {
+            enum_name: name, ..
+        } => codebase
+            .classlike_infos
+            .get(name)
+            .map_or(true, |storage| storage.enum_constraint.is_none()),
+        TAtomic::TTypeAlias {
+            as_type: Some(_), ..
+        }
+        | TAtomic::TGenericParam { .. } => true,
+        _ => false,
+    }
+}
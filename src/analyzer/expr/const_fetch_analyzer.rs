@@ -1,14 +1,18 @@
 use std::path::Path;
 use std::rc::Rc;
 
+use hakana_reflection_info::class_constant_info::ConstantInfo;
+use hakana_reflection_info::codebase_info::CodebaseInfo;
 use hakana_reflection_info::issue::Issue;
 use hakana_reflection_info::issue::IssueKind;
+use hakana_reflection_info::t_union::TUnion;
 use hakana_str::StrId;
 use hakana_type::get_literal_string;
 use hakana_type::get_mixed_any;
 use hakana_type::get_string;
 use hakana_type::type_expander;
 use hakana_type::type_expander::TypeExpansionOptions;
+use rustc_hash::FxHashSet;
 
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::scope_analyzer::ScopeAnalyzer;
@@ -54,6 +58,8 @@ pub(crate) fn analyze(
             get_string()
         } else if let Some(t) = &constant_storage.inferred_type {
             t.clone()
+        } else if let Some(t) = get_aliased_constant_type(codebase, constant_storage) {
+            t
         } else if let Some(t) = &constant_storage.provided_type {
             t.clone()
         } else {
@@ -93,3 +99,28 @@ pub(crate) fn analyze(
 
     Ok(())
 }
+
+/// Follows a chain of constants defined as other constants to the first one
+/// with a known type
+fn get_aliased_constant_type(
+    codebase: &CodebaseInfo,
+    constant_storage: &ConstantInfo,
+) -> Option<TUnion> {
+    let mut seen_constants = FxHashSet::default();
+    let mut constant_storage = constant_storage;
+
+    while let Some(aliased_constant) = constant_storage.aliased_constant {
+        // constants defined in terms of each other never resolve
+        if !seen_constants.insert(aliased_constant) {
+            return None;
+        }
+
+        constant_storage = codebase.constant_infos.get(&aliased_constant)?;
+
+        if let Some(t) = &constant_storage.inferred_type {
+            return Some(t.clone());
+        }
+    }
+
+    constant_storage.provided_type.clone()
+}
@@ -3,16 +3,18 @@ use std::rc::Rc;
 use crate::expression_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::scope::BlockContext;
+use crate::scope_analyzer::ScopeAnalyzer;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt_analyzer::AnalysisError;
 use hakana_reflection_info::code_location::HPos;
 use hakana_reflection_info::function_context::FunctionLikeIdentifier;
 use hakana_reflection_info::functionlike_parameter::FunctionLikeParameter;
+use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_reflection_info::t_union::TUnion;
 use hakana_reflection_info::{VarId, EFFECT_IMPURE};
 use hakana_str::StrId;
-use hakana_type::get_mixed_any;
+use hakana_type::{get_mixed, get_mixed_any};
 use oxidized::ast_defs::Pos;
 use oxidized::{aast, ast_defs};
 
@@ -40,14 +42,47 @@ pub(crate) fn analyze(
             &mut None,
         )?;
 
-        let arg_type = analysis_data.get_rc_expr_type(arg_expr.pos()).cloned();
+        let arg_type = analysis_data
+            .get_rc_expr_type(arg_expr.pos())
+            .cloned()
+            .unwrap_or(Rc::new(get_mixed_any()));
+
+        // objects are already caught by the argument check below, but echoing a vec,
+        // dict or keyset is worth its own issue
+        let has_container_operand = arg_type.types.iter().any(|atomic| {
+            matches!(
+                atomic,
+                TAtomic::TVec { .. } | TAtomic::TDict { .. } | TAtomic::TKeyset { .. }
+            )
+        });
+
+        if has_container_operand {
+            analysis_data.maybe_add_issue(
+                Issue::new(
+                    IssueKind::InvalidEchoOperand,
+                    format!(
+                        "Cannot echo value of type {}",
+                        arg_type.get_id(Some(statements_analyzer.get_interner()))
+                    ),
+                    statements_analyzer.get_hpos(arg_expr.pos()),
+                    &context.function_context.calling_functionlike_id,
+                ),
+                statements_analyzer.get_config(),
+                statements_analyzer.get_file_path_actual(),
+            );
+        }
 
         context.inside_general_use = true;
 
+        // the operand still flows into the output sinks either way
         argument_analyzer::verify_type(
             statements_analyzer,
-            &arg_type.unwrap_or(Rc::new(get_mixed_any())),
-            &TUnion::new(vec![TAtomic::TScalar, TAtomic::TNull]),
+            &arg_type,
+            &if has_container_operand {
+                get_mixed()
+            } else {
+                TUnion::new(vec![TAtomic::TScalar, TAtomic::TNull])
+            },
             &FunctionLikeIdentifier::Function(StrId::ECHO),
             i,
             arg_expr,
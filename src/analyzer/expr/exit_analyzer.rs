@@ -70,5 +70,7 @@ pub(crate) fn analyze(
 
     analysis_data.set_expr_type(call_pos, get_nothing());
 
+    context.has_returned = true;
+
     Ok(())
 }
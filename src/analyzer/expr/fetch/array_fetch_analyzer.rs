@@ -1004,7 +1004,7 @@ pub(crate) fn handle_array_access_on_mixed(
     }
 
     if let TAtomic::TNothing = mixed {
-        return get_mixed_maybe_from_loop(true);
+        return get_mixed_maybe_from_loop(!statements_analyzer.get_config().widen_loop_isset_mixed);
     }
 
     get_mixed_any()
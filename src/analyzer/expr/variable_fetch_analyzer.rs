@@ -34,6 +34,7 @@ pub(crate) fn analyze(
                     lid.1 .1[1..].to_string(),
                     pos,
                     analysis_data,
+                    false,
                 ));
 
                 context
@@ -145,52 +146,95 @@ pub(crate) fn analyze(
     Ok(())
 }
 
+// Command-line arguments are only treated as user input inside an <<__EntryPoint>>,
+// since that's the only place a CLI script can receive them from
 pub(crate) fn get_type_for_superglobal(
     statements_analyzer: &StatementsAnalyzer,
     name: String,
     pos: &Pos,
     analysis_data: &mut FunctionAnalysisData,
+    in_entry_point: bool,
 ) -> TUnion {
     match name.as_str() {
         "_FILES" | "_SERVER" | "_ENV" => get_mixed_dict(),
         "_GET" | "_REQUEST" | "_POST" | "_COOKIE" => {
             let mut var_type = get_mixed_dict();
 
-            let taint_pos = statements_analyzer.get_hpos(pos);
-            let taint_source = DataFlowNode {
-                id: DataFlowNodeId::Var(
-                    VarId(
-                        statements_analyzer
-                            .get_interner()
-                            .get(&format!("${}", name))
-                            .unwrap(),
-                    ),
-                    taint_pos.file_path,
-                    taint_pos.start_offset,
-                    taint_pos.end_offset,
-                ),
-                kind: DataFlowNodeKind::TaintSource {
-                    pos: None,
-                    types: if name == "_GET" || name == "_REQUEST" {
-                        vec![SourceType::UriRequestHeader]
-                    } else {
-                        vec![SourceType::NonUriRequestHeader]
-                    },
+            add_superglobal_taint_source(
+                statements_analyzer,
+                &name,
+                pos,
+                analysis_data,
+                &mut var_type,
+                if name == "_GET" || name == "_REQUEST" {
+                    vec![SourceType::UriRequestHeader]
+                } else {
+                    vec![SourceType::NonUriRequestHeader]
                 },
-            };
+            );
 
-            analysis_data.data_flow_graph.add_node(taint_source.clone());
+            var_type
+        }
+        "argv" => {
+            let mut var_type = get_mixed_any();
 
-            var_type.parent_nodes.push(taint_source);
+            if in_entry_point {
+                add_superglobal_taint_source(
+                    statements_analyzer,
+                    &name,
+                    pos,
+                    analysis_data,
+                    &mut var_type,
+                    vec![SourceType::RawUserData],
+                );
+            }
 
             var_type
         }
-        "argv" => get_mixed_any(),
         "argc" => get_int(),
         _ => get_mixed_any(),
     }
 }
 
+fn add_superglobal_taint_source(
+    statements_analyzer: &StatementsAnalyzer,
+    name: &str,
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    var_type: &mut TUnion,
+    source_types: Vec<SourceType>,
+) {
+    let taint_pos = statements_analyzer.get_hpos(pos);
+    let var_name = format!("${}", name);
+
+    let taint_source = DataFlowNode {
+        // HH\global_get('argv') doesn't need $argv to appear anywhere in the codebase
+        id: if let Some(var_id) = statements_analyzer.get_interner().get(&var_name) {
+            DataFlowNodeId::Var(
+                VarId(var_id),
+                taint_pos.file_path,
+                taint_pos.start_offset,
+                taint_pos.end_offset,
+            )
+        } else {
+            DataFlowNodeId::LocalString(
+                var_name,
+                taint_pos.file_path,
+                taint_pos.start_offset,
+                taint_pos.end_offset,
+            )
+        },
+        kind: DataFlowNodeKind::TaintSource {
+            pos: None,
+            types: source_types,
+        },
+    };
+
+    analysis_data.data_flow_graph.add_node(taint_source.clone());
+
+    var_type.parent_nodes.push(taint_source);
+}
+
 fn add_dataflow_to_variable(
     statements_analyzer: &StatementsAnalyzer,
     lid: &Lid,
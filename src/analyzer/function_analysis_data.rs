@@ -42,9 +42,12 @@ pub struct FunctionAnalysisData {
     pub matched_ignore_positions: FxHashSet<(u32, u32)>,
     pub type_variable_bounds: FxHashMap<String, (Vec<TemplateBound>, Vec<TemplateBound>)>,
     pub migrate_function: Option<bool>,
+    pub has_dynamic_calls: bool,
     pub after_expr_hook_called: FxHashSet<(u32, u32)>,
     pub after_arg_hook_called: FxHashSet<(u32, u32)>,
     pub inside_await: bool,
+    // maps each applied HAKANA_ASSERT comment to the statement it applies to
+    pub inline_assertion_stmts: FxHashMap<u32, u32>,
 }
 
 impl FunctionAnalysisData {
@@ -87,9 +90,11 @@ impl FunctionAnalysisData {
             issue_counts: FxHashMap::default(),
             type_variable_bounds: FxHashMap::default(),
             migrate_function: None,
+            has_dynamic_calls: false,
             after_arg_hook_called: FxHashSet::default(),
             after_expr_hook_called: FxHashSet::default(),
             inside_await: false,
+            inline_assertion_stmts: FxHashMap::default(),
         }
     }
 
@@ -13,7 +13,7 @@ use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt::return_analyzer::handle_inout_at_return;
 use crate::stmt_analyzer::AnalysisError;
 use crate::{file_analyzer::FileAnalyzer, function_analysis_data::FunctionAnalysisData};
-use hakana_reflection_info::analysis_result::{AnalysisResult, Replacement};
+use hakana_reflection_info::analysis_result::{AnalysisResult, Replacement, TypeCoverage};
 use hakana_reflection_info::classlike_info::ClassLikeInfo;
 use hakana_reflection_info::code_location::{FilePath, HPos, StmtStart};
 use hakana_reflection_info::codebase_info::CodebaseInfo;
@@ -470,6 +470,10 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                 .type_variable_bounds
                 .clone_from(&parent_analysis_data.type_variable_bounds);
 
+            analysis_data
+                .inline_assertion_stmts
+                .clone_from(&parent_analysis_data.inline_assertion_stmts);
+
             if statements_analyzer.get_config().in_migration {
                 analysis_data.data_flow_graph = parent_analysis_data.data_flow_graph.clone();
             }
@@ -672,6 +676,29 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                 &mut analysis_data.data_flow_graph,
             );
 
+            if completed_analysis
+                && !context.has_returned
+                && expected_return_type.is_nothing()
+                && !functionlike_storage.is_async
+                && !functionlike_storage.has_yield
+            {
+                if let Some(return_type_location) = functionlike_storage.return_type_location {
+                    analysis_data.maybe_add_issue(
+                        Issue::new(
+                            IssueKind::InvalidReturnType,
+                            format!(
+                                "{} is declared noreturn, but can finish without throwing or exiting",
+                                functionlike_id.to_string(statements_analyzer.get_interner())
+                            ),
+                            return_type_location,
+                            &context.function_context.calling_functionlike_id,
+                        ),
+                        statements_analyzer.get_config(),
+                        statements_analyzer.get_file_path_actual(),
+                    );
+                }
+            }
+
             let config = statements_analyzer.get_config();
 
             let return_result_handled = config.hooks.iter().any(|hook| {
@@ -821,6 +848,8 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                 parent_analysis_data.migrate_function = analysis_data.migrate_function;
             }
 
+            parent_analysis_data.has_dynamic_calls |= analysis_data.has_dynamic_calls;
+
             if statements_analyzer.get_config().add_fixmes {
                 parent_analysis_data
                     .expr_fixme_positions
@@ -871,6 +900,26 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                     .insert(context.function_context.calling_functionlike_id.unwrap(), b);
             }
 
+            if analysis_data.has_dynamic_calls {
+                if let Some(functionlike_id) = context.function_context.calling_functionlike_id {
+                    analysis_result
+                        .functions_with_dynamic_calls
+                        .insert(functionlike_id);
+                }
+            }
+
+            if statements_analyzer.get_config().collect_type_coverage {
+                if let (GraphKind::FunctionBody, Some(functionlike_id)) = (
+                    &analysis_data.data_flow_graph.kind,
+                    context.function_context.calling_functionlike_id,
+                ) {
+                    analysis_result.type_coverage.insert(
+                        functionlike_id,
+                        get_type_coverage(&analysis_data, functionlike_storage, &functionlike_id),
+                    );
+                }
+            }
+
             update_analysis_result_with_tast(
                 analysis_data,
                 analysis_result,
@@ -878,7 +927,8 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                     .get_file_analyzer()
                     .get_file_source()
                     .file_path,
-                functionlike_storage.ignore_taint_path,
+                // entry points still contribute their own body, since that's where argv enters
+                functionlike_storage.ignore_taint_path && !functionlike_storage.is_entry_point,
             );
         }
 
@@ -1260,30 +1310,49 @@ fn report_unused_expressions(
                     }
                 }
 
+                let should_report_param = match &kind {
+                    VariableSourceKind::PrivateParam => true,
+                    // public and protected parameters can be part of a signature
+                    // shared with other classes
+                    VariableSourceKind::NonPrivateParam => {
+                        config.find_unused_method_parameters
+                            && !has_inherited_signature(
+                                statements_analyzer.get_codebase(),
+                                calling_functionlike_id,
+                                functionlike_storage,
+                            )
+                    }
+                    VariableSourceKind::ClosureParam
+                    | VariableSourceKind::Default
+                    | VariableSourceKind::InoutParam => false,
+                };
+
                 match &kind {
-                    VariableSourceKind::PrivateParam => {
-                        let pos = get_param_pos(functionlike_storage, &node.id);
-
-                        analysis_data.expr_fixme_positions.insert(
-                            (pos.start_offset, pos.end_offset),
-                            StmtStart {
-                                offset: pos.start_offset,
-                                line: pos.start_line,
-                                column: pos.start_column,
-                                add_newline: functionlike_storage.has_multi_line_params(),
-                            },
-                        );
+                    VariableSourceKind::PrivateParam | VariableSourceKind::NonPrivateParam => {
+                        if should_report_param {
+                            let pos = get_param_pos(functionlike_storage, &node.id);
+
+                            analysis_data.expr_fixme_positions.insert(
+                                (pos.start_offset, pos.end_offset),
+                                StmtStart {
+                                    offset: pos.start_offset,
+                                    line: pos.start_line,
+                                    column: pos.start_column,
+                                    add_newline: functionlike_storage.has_multi_line_params(),
+                                },
+                            );
 
-                        analysis_data.maybe_add_issue(
-                            Issue::new(
-                                IssueKind::UnusedParameter,
-                                "Unused param ".to_string() + &node.id.to_label(interner),
-                                pos,
-                                calling_functionlike_id,
-                            ),
-                            statements_analyzer.get_config(),
-                            statements_analyzer.get_file_path_actual(),
-                        );
+                            analysis_data.maybe_add_issue(
+                                Issue::new(
+                                    IssueKind::UnusedParameter,
+                                    "Unused param ".to_string() + &node.id.to_label(interner),
+                                    pos,
+                                    calling_functionlike_id,
+                                ),
+                                statements_analyzer.get_config(),
+                                statements_analyzer.get_file_path_actual(),
+                            );
+                        }
                     }
                     VariableSourceKind::ClosureParam => {
                         if config
@@ -1311,9 +1380,6 @@ fn report_unused_expressions(
                             );
                         }
                     }
-                    VariableSourceKind::NonPrivateParam => {
-                        // todo register public/private param
-                    }
                     VariableSourceKind::Default => {
                         handle_unused_assignment(
                             config,
@@ -1348,6 +1414,52 @@ fn report_unused_expressions(
     }
 }
 
+/// Whether a public or protected method's parameters are dictated by (or dictate)
+/// another class's signature, so that an unused one can't simply be removed
+fn has_inherited_signature(
+    codebase: &CodebaseInfo,
+    calling_functionlike_id: &Option<FunctionLikeIdentifier>,
+    functionlike_storage: &FunctionLikeInfo,
+) -> bool {
+    let (classlike_name, method_name) =
+        if let Some(FunctionLikeIdentifier::Method(classlike_name, method_name)) =
+            calling_functionlike_id
+        {
+            (classlike_name, method_name)
+        } else {
+            return true;
+        };
+
+    if functionlike_storage.overriding
+        || functionlike_storage.dynamically_callable
+        || codebase.trait_exists(classlike_name)
+    {
+        return true;
+    }
+
+    if let Some(classlike_storage) = codebase.classlike_infos.get(classlike_name) {
+        if classlike_storage
+            .overridden_method_ids
+            .contains_key(method_name)
+        {
+            return true;
+        }
+    } else {
+        return true;
+    }
+
+    // child constructors don't have to match their parent's signature
+    *method_name != StrId::CONSTRUCT
+        && codebase
+            .get_all_descendants(classlike_name)
+            .iter()
+            .any(|descendant| {
+                codebase
+                    .functionlike_infos
+                    .contains_key(&(*descendant, *method_name))
+            })
+}
+
 fn get_param_pos(functionlike_storage: &FunctionLikeInfo, id: &DataFlowNodeId) -> HPos {
     if let DataFlowNodeId::Param(var_id, ..) = id {
         functionlike_storage
@@ -1447,6 +1559,49 @@ fn handle_unused_assignment(
     }
 }
 
+// Every expression counts once, so functions with only a handful of expressions
+// don't weigh as much as big untyped ones when coverage is aggregated
+fn get_type_coverage(
+    analysis_data: &FunctionAnalysisData,
+    functionlike_storage: &FunctionLikeInfo,
+    functionlike_id: &FunctionLikeIdentifier,
+) -> TypeCoverage {
+    let mut type_coverage = TypeCoverage::default();
+
+    let mut add_type = |t: Option<&TUnion>| {
+        type_coverage.total_count += 1;
+
+        if let Some(t) = t {
+            if !t.types.iter().any(|atomic| {
+                matches!(
+                    atomic,
+                    TAtomic::TMixed | TAtomic::TMixedWithFlags(..) | TAtomic::TMixedFromLoopIsset
+                )
+            }) {
+                type_coverage.typed_count += 1;
+            }
+        }
+    };
+
+    for param in &functionlike_storage.params {
+        add_type(param.signature_type.as_ref());
+    }
+
+    // constructors never have a return type
+    if !matches!(
+        functionlike_id,
+        FunctionLikeIdentifier::Method(_, StrId::CONSTRUCT)
+    ) {
+        add_type(functionlike_storage.return_type.as_ref());
+    }
+
+    for expr_type in analysis_data.expr_types.values() {
+        add_type(Some(expr_type));
+    }
+
+    type_coverage
+}
+
 pub(crate) fn update_analysis_result_with_tast(
     analysis_data: FunctionAnalysisData,
     analysis_result: &mut AnalysisResult,
@@ -50,7 +50,10 @@ pub(crate) fn reconcile_keyed_types(
         return;
     }
 
-    let inside_loop = context.inside_loop;
+    // mixed values produced by isset checks inside a loop are normally kept distinct so the
+    // next iteration can narrow them, but projects can opt into treating them as plain mixed
+    let inside_loop =
+        context.inside_loop && !statements_analyzer.get_config().widen_loop_isset_mixed;
 
     let old_new_types = new_types.clone();
 
@@ -124,6 +127,20 @@ pub(crate) fn reconcile_keyed_types(
                     continue;
                 }
 
+                if key == "hakana functions" {
+                    match assertion {
+                        Assertion::FunctionExists(function_name) => {
+                            context.functions_known_to_exist.insert(*function_name);
+                        }
+                        Assertion::FunctionDoesNotExist(function_name) => {
+                            context.functions_known_to_exist.remove(function_name);
+                        }
+                        _ => (),
+                    }
+
+                    continue;
+                }
+
                 if assertion.has_negation() {
                     has_negation = true;
                 }
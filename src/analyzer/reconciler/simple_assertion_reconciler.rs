@@ -6,7 +6,7 @@ use crate::{
     statements_analyzer::StatementsAnalyzer,
 };
 use hakana_reflection_info::{
-    assertion::Assertion,
+    assertion::{Assertion, SubstringPosition},
     codebase_info::CodebaseInfo,
     functionlike_identifier::FunctionLikeIdentifier,
     t_atomic::{DictKey, TAtomic},
@@ -14,9 +14,10 @@ use hakana_reflection_info::{
 };
 use hakana_str::StrId;
 use hakana_type::{
-    get_arraykey, get_bool, get_false, get_float, get_int, get_keyset, get_mixed_any,
-    get_mixed_dict, get_mixed_keyset, get_mixed_maybe_from_loop, get_mixed_vec, get_nothing,
-    get_null, get_num, get_object, get_scalar, get_string, get_true, intersect_union_types,
+    combine_union_types, get_arraykey, get_bool, get_false, get_float, get_int, get_keyset,
+    get_mixed_any, get_mixed_dict, get_mixed_keyset, get_mixed_maybe_from_loop, get_mixed_vec,
+    get_nothing, get_null, get_num, get_object, get_scalar, get_string, get_true, get_value_param,
+    intersect_union_types,
     template::TemplateBound,
     type_comparator::{
         atomic_type_comparator, type_comparison_result::TypeComparisonResult, union_type_comparator,
@@ -439,6 +440,42 @@ pub(crate) fn reconcile(
             false,
             count,
         )),
+        Assertion::IsList => Some(reconcile_list(
+            assertion,
+            existing_var_type,
+            key,
+            negated,
+            analysis_data,
+            statements_analyzer,
+            pos,
+            calling_functionlike_id,
+            suppressed_issues,
+        )),
+        Assertion::IsCallable => Some(reconcile_callable(
+            assertion,
+            existing_var_type,
+            key,
+            negated,
+            analysis_data,
+            statements_analyzer,
+            pos,
+            calling_functionlike_id,
+            suppressed_issues,
+        )),
+        Assertion::HasSubstring(position, substring) => Some(reconcile_substring(
+            assertion,
+            existing_var_type,
+            key,
+            negated,
+            analysis_data,
+            statements_analyzer,
+            pos,
+            calling_functionlike_id,
+            suppressed_issues,
+            position,
+            substring,
+            true,
+        )),
         _ => None,
     }
 }
@@ -1700,6 +1737,300 @@ fn reconcile_non_empty_countable(
     new_var_type
 }
 
+fn reconcile_list(
+    assertion: &Assertion,
+    existing_var_type: &TUnion,
+    key: Option<&String>,
+    negated: bool,
+    analysis_data: &mut FunctionAnalysisData,
+    statements_analyzer: &StatementsAnalyzer,
+    pos: Option<&Pos>,
+    calling_functionlike_id: &Option<FunctionLikeIdentifier>,
+    suppressed_issues: &FxHashMap<String, usize>,
+) -> TUnion {
+    let codebase = statements_analyzer.get_codebase();
+
+    let mut did_remove_type = existing_var_type.possibly_undefined_from_try;
+
+    let mut new_var_type = existing_var_type.clone();
+
+    let existing_var_types = new_var_type.types.drain(..).collect::<Vec<_>>();
+
+    let mut acceptable_types = vec![];
+
+    new_var_type.possibly_undefined_from_try = false;
+
+    for atomic in existing_var_types {
+        match atomic {
+            // a vec is always a list
+            TAtomic::TVec { .. } => {
+                acceptable_types.push(atomic);
+            }
+            TAtomic::TDict {
+                params: None,
+                ref known_items,
+                non_empty,
+                ..
+            } => {
+                did_remove_type = true;
+
+                if let Some(list_items) = get_list_items_from_sealed_dict(known_items) {
+                    let has_optional_items = list_items.values().any(|(u, _)| *u);
+
+                    let known_count = if has_optional_items {
+                        None
+                    } else {
+                        Some(list_items.len())
+                    };
+
+                    if list_items.len() > 1 {
+                        // dicts don't record insertion order, so with more than one key we
+                        // know the list's values but not which offset each one ends up at
+                        let mut type_param = get_nothing();
+
+                        for (_, item_type) in list_items.values() {
+                            type_param =
+                                combine_union_types(item_type, &type_param, codebase, false);
+                        }
+
+                        acceptable_types.push(TAtomic::TVec {
+                            known_count,
+                            known_items: None,
+                            type_param: Box::new(type_param),
+                            non_empty,
+                        });
+                    } else {
+                        acceptable_types.push(TAtomic::TVec {
+                            known_count,
+                            known_items: if list_items.is_empty() {
+                                None
+                            } else {
+                                Some(list_items)
+                            },
+                            type_param: Box::new(get_nothing()),
+                            non_empty,
+                        });
+                    }
+                }
+            }
+            TAtomic::TDict {
+                params: Some((ref key_param, _)),
+                non_empty,
+                ..
+            } => {
+                did_remove_type = true;
+
+                if key_param.types.iter().all(|t| t.is_string()) {
+                    // only an empty dict with string keys is a list
+                    if !non_empty {
+                        acceptable_types.push(TAtomic::TVec {
+                            known_items: None,
+                            type_param: Box::new(get_nothing()),
+                            known_count: Some(0),
+                            non_empty: false,
+                        });
+                    }
+                } else if let Some(value_param) = get_value_param(&atomic, codebase) {
+                    acceptable_types.push(TAtomic::TVec {
+                        known_items: None,
+                        type_param: Box::new(value_param),
+                        known_count: None,
+                        non_empty,
+                    });
+                }
+            }
+            TAtomic::TMixed | TAtomic::TMixedWithFlags(..) | TAtomic::TMixedFromLoopIsset => {
+                did_remove_type = true;
+
+                acceptable_types.extend(get_mixed_vec().types);
+            }
+            _ => {
+                did_remove_type = true;
+
+                acceptable_types.push(atomic);
+            }
+        }
+    }
+
+    get_acceptable_type(
+        acceptable_types,
+        did_remove_type,
+        key,
+        pos,
+        calling_functionlike_id,
+        existing_var_type,
+        statements_analyzer,
+        analysis_data,
+        assertion,
+        negated,
+        suppressed_issues,
+        new_var_type,
+    )
+}
+
+// Keeps string callables, vecs of the form [$obj, 'method'], closures, and objects
+// (which may implement __invoke)
+fn reconcile_callable(
+    assertion: &Assertion,
+    existing_var_type: &TUnion,
+    key: Option<&String>,
+    negated: bool,
+    analysis_data: &mut FunctionAnalysisData,
+    statements_analyzer: &StatementsAnalyzer,
+    pos: Option<&Pos>,
+    calling_functionlike_id: &Option<FunctionLikeIdentifier>,
+    suppressed_issues: &FxHashMap<String, usize>,
+) -> TUnion {
+    let mut did_remove_type = existing_var_type.possibly_undefined_from_try;
+
+    let mut new_var_type = existing_var_type.clone();
+
+    let existing_var_types = new_var_type.types.drain(..).collect::<Vec<_>>();
+
+    let mut acceptable_types = vec![];
+
+    new_var_type.possibly_undefined_from_try = false;
+
+    for atomic in existing_var_types {
+        match atomic {
+            TAtomic::TClosure { .. }
+            | TAtomic::TClosureAlias { .. }
+            | TAtomic::TVec { .. }
+            | TAtomic::TNamedObject { .. }
+            | TAtomic::TObject
+            | TAtomic::TGenericParam { .. }
+            | TAtomic::TTypeAlias { .. }
+            | TAtomic::TClassTypeConstant { .. }
+            | TAtomic::TString
+            | TAtomic::TStringWithFlags(..)
+            | TAtomic::TLiteralString { .. }
+            | TAtomic::TLiteralClassname { .. } => {
+                acceptable_types.push(atomic);
+            }
+            TAtomic::TMixed | TAtomic::TMixedWithFlags(..) | TAtomic::TMixedFromLoopIsset => {
+                did_remove_type = true;
+
+                acceptable_types.push(TAtomic::TMixedWithFlags(false, false, false, true));
+            }
+            TAtomic::TArraykey { .. } | TAtomic::TScalar => {
+                did_remove_type = true;
+
+                acceptable_types.push(TAtomic::TString);
+            }
+            _ => {
+                did_remove_type = true;
+            }
+        }
+    }
+
+    get_acceptable_type(
+        acceptable_types,
+        did_remove_type,
+        key,
+        pos,
+        calling_functionlike_id,
+        existing_var_type,
+        statements_analyzer,
+        analysis_data,
+        assertion,
+        negated,
+        suppressed_issues,
+        new_var_type,
+    )
+}
+
+// Unions of literal strings can be enormous, and we don't want to scan all of them
+// on every condition
+const MAX_SUBSTRING_REFINEMENT_TYPES: usize = 256;
+
+/// Keeps the literal strings that do (or, when `has_substring` is false, don't) contain
+/// the given substring. Non-literal string types are always kept.
+pub(crate) fn reconcile_substring(
+    assertion: &Assertion,
+    existing_var_type: &TUnion,
+    key: Option<&String>,
+    negated: bool,
+    analysis_data: &mut FunctionAnalysisData,
+    statements_analyzer: &StatementsAnalyzer,
+    pos: Option<&Pos>,
+    calling_functionlike_id: &Option<FunctionLikeIdentifier>,
+    suppressed_issues: &FxHashMap<String, usize>,
+    position: &SubstringPosition,
+    substring: &str,
+    has_substring: bool,
+) -> TUnion {
+    if existing_var_type.types.len() > MAX_SUBSTRING_REFINEMENT_TYPES {
+        return existing_var_type.clone();
+    }
+
+    let mut did_remove_type = existing_var_type.possibly_undefined_from_try;
+
+    let mut new_var_type = existing_var_type.clone();
+
+    let existing_var_types = new_var_type.types.drain(..).collect::<Vec<_>>();
+
+    let mut acceptable_types = vec![];
+
+    new_var_type.possibly_undefined_from_try = false;
+
+    for atomic in existing_var_types {
+        if let TAtomic::TLiteralString { value } = &atomic {
+            if position.matches(value, substring) == has_substring {
+                acceptable_types.push(atomic);
+            } else {
+                did_remove_type = true;
+            }
+        } else {
+            did_remove_type = true;
+
+            acceptable_types.push(atomic);
+        }
+    }
+
+    get_acceptable_type(
+        acceptable_types,
+        did_remove_type,
+        key,
+        pos,
+        calling_functionlike_id,
+        existing_var_type,
+        statements_analyzer,
+        analysis_data,
+        assertion,
+        negated,
+        suppressed_issues,
+        new_var_type,
+    )
+}
+
+/// Returns the items of a sealed dict as vec items if its keys can form a list,
+/// i.e. its int keys run contiguously from zero and any other keys are optional
+pub(crate) fn get_list_items_from_sealed_dict(
+    known_items: &Option<BTreeMap<DictKey, (bool, Arc<TUnion>)>>,
+) -> Option<BTreeMap<usize, (bool, TUnion)>> {
+    let mut list_items = BTreeMap::new();
+
+    if let Some(known_items) = known_items {
+        for (dict_key, (possibly_undefined, item_type)) in known_items {
+            match dict_key {
+                DictKey::Int(offset) if *offset == list_items.len() as u64 => {
+                    list_items.insert(
+                        list_items.len(),
+                        (*possibly_undefined, (**item_type).clone()),
+                    );
+                }
+                _ => {
+                    if !possibly_undefined {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    Some(list_items)
+}
+
 fn reconcile_exactly_countable(
     assertion: &Assertion,
     existing_var_type: &TUnion,
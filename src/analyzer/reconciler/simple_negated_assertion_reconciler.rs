@@ -1,4 +1,6 @@
-use super::simple_assertion_reconciler::{get_acceptable_type, intersect_null};
+use super::simple_assertion_reconciler::{
+    get_acceptable_type, get_list_items_from_sealed_dict, intersect_null, reconcile_substring,
+};
 use crate::{
     function_analysis_data::FunctionAnalysisData, reconciler::trigger_issue_for_impossible,
     scope_analyzer::ScopeAnalyzer, statements_analyzer::StatementsAnalyzer,
@@ -312,10 +314,159 @@ pub(crate) fn reconcile(
             suppressed_issues,
             count,
         )),
+        Assertion::IsNotList => Some(reconcile_not_list(
+            assertion,
+            existing_var_type,
+            key,
+            negated,
+            analysis_data,
+            statements_analyzer,
+            pos,
+            calling_functionlike_id,
+            suppressed_issues,
+        )),
+        Assertion::IsNotCallable => Some(reconcile_not_callable(
+            assertion,
+            existing_var_type,
+            key,
+            negated,
+            analysis_data,
+            statements_analyzer,
+            pos,
+            calling_functionlike_id,
+            suppressed_issues,
+        )),
+        Assertion::DoesNotHaveSubstring(position, substring) => Some(reconcile_substring(
+            assertion,
+            existing_var_type,
+            key,
+            negated,
+            analysis_data,
+            statements_analyzer,
+            pos,
+            calling_functionlike_id,
+            suppressed_issues,
+            position,
+            substring,
+            false,
+        )),
         _ => None,
     };
 }
 
+fn reconcile_not_list(
+    assertion: &Assertion,
+    existing_var_type: &TUnion,
+    key: Option<&String>,
+    negated: bool,
+    analysis_data: &mut FunctionAnalysisData,
+    statements_analyzer: &StatementsAnalyzer,
+    pos: Option<&Pos>,
+    calling_functionlike_id: &Option<FunctionLikeIdentifier>,
+    suppressed_issues: &FxHashMap<String, usize>,
+) -> TUnion {
+    let mut did_remove_type = existing_var_type.possibly_undefined_from_try;
+
+    let mut new_var_type = existing_var_type.clone();
+
+    let existing_var_types = new_var_type.types.drain(..).collect::<Vec<_>>();
+
+    let mut acceptable_types = vec![];
+
+    new_var_type.possibly_undefined_from_try = false;
+
+    for atomic in existing_var_types {
+        match atomic {
+            // a vec is always a list
+            TAtomic::TVec { .. } => {
+                did_remove_type = true;
+            }
+            TAtomic::TDict {
+                params: None,
+                ref known_items,
+                ..
+            } => {
+                if let Some(list_items) = get_list_items_from_sealed_dict(known_items) {
+                    did_remove_type = true;
+
+                    // with more than one key the dict may have been built out of order
+                    if list_items.len() > 1 || list_items.values().any(|(u, _)| *u) {
+                        acceptable_types.push(atomic);
+                    }
+                } else {
+                    acceptable_types.push(atomic);
+                }
+            }
+            _ => {
+                did_remove_type = true;
+
+                acceptable_types.push(atomic);
+            }
+        }
+    }
+
+    get_acceptable_type(
+        acceptable_types,
+        did_remove_type,
+        key,
+        pos,
+        calling_functionlike_id,
+        existing_var_type,
+        statements_analyzer,
+        analysis_data,
+        assertion,
+        negated,
+        suppressed_issues,
+        new_var_type,
+    )
+}
+
+// Only closures are always callable — strings, vecs and objects may or may not be
+fn reconcile_not_callable(
+    assertion: &Assertion,
+    existing_var_type: &TUnion,
+    key: Option<&String>,
+    negated: bool,
+    analysis_data: &mut FunctionAnalysisData,
+    statements_analyzer: &StatementsAnalyzer,
+    pos: Option<&Pos>,
+    calling_functionlike_id: &Option<FunctionLikeIdentifier>,
+    suppressed_issues: &FxHashMap<String, usize>,
+) -> TUnion {
+    let mut did_remove_type = existing_var_type.possibly_undefined_from_try;
+
+    let mut new_var_type = existing_var_type.clone();
+
+    let existing_var_types = new_var_type.types.drain(..).collect::<Vec<_>>();
+
+    let mut acceptable_types = vec![];
+
+    new_var_type.possibly_undefined_from_try = false;
+
+    for atomic in existing_var_types {
+        if let TAtomic::TClosure { .. } | TAtomic::TClosureAlias { .. } = atomic {
+            did_remove_type = true;
+        } else {
+            acceptable_types.push(atomic);
+        }
+    }
+
+    get_acceptable_type(
+        acceptable_types,
+        did_remove_type,
+        key,
+        pos,
+        calling_functionlike_id,
+        existing_var_type,
+        statements_analyzer,
+        analysis_data,
+        assertion,
+        negated,
+        suppressed_issues,
+        new_var_type,
+    )
+}
+
 fn subtract_object(
     assertion: &Assertion,
     existing_var_type: &TUnion,
@@ -5,6 +5,7 @@ use hakana_algebra::Clause;
 use hakana_reflection_info::function_context::FunctionContext;
 use hakana_reflection_info::EFFECT_PURE;
 use hakana_reflection_info::{assertion::Assertion, t_union::TUnion};
+use hakana_str::StrId;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
@@ -162,6 +163,9 @@ pub struct BlockContext {
 
     pub allow_taints: bool,
 
+    /// functions that a function_exists check says we can call, even if Hakana can't see them
+    pub functions_known_to_exist: FxHashSet<StrId>,
+
     pub inside_async: bool,
 
     pub for_loop_init_bounds: (u32, u32),
@@ -205,6 +209,7 @@ impl BlockContext {
             calling_closure_id: None,
             parent_conflicting_clause_vars: FxHashSet::default(),
             allow_taints: true,
+            functions_known_to_exist: FxHashSet::default(),
             inside_async: false,
             for_loop_init_bounds: (0, 0),
 
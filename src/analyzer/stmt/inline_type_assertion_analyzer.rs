@@ -0,0 +1,211 @@
+use std::rc::Rc;
+
+use crate::function_analysis_data::FunctionAnalysisData;
+use crate::scope::BlockContext;
+use crate::scope_analyzer::ScopeAnalyzer;
+use crate::statements_analyzer::StatementsAnalyzer;
+use hakana_reflection_info::data_flow::graph::{DataFlowGraph, GraphKind};
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_union::{populate_union_type, TUnion};
+use hakana_reflector::typehint_resolver::get_type_from_string;
+use hakana_str::StrId;
+use hakana_type::type_comparator::union_type_comparator;
+use hakana_type::type_expander::{self, TypeExpansionOptions};
+use oxidized::pos::Pos;
+use oxidized::prim_defs::Comment;
+
+/// Applies `/* HAKANA_ASSERT $x: SomeType */` comments that appear directly above
+/// (or on the same line as) a statement, overriding the inferred type of the variable.
+/// Each comment only applies to the first statement after it, not to statements
+/// nested inside that one.
+pub(crate) fn analyze(
+    statements_analyzer: &StatementsAnalyzer,
+    stmt_pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut BlockContext,
+) {
+    let stmt_start = stmt_pos.start_offset() as u32;
+
+    let assertions = statements_analyzer
+        .comments
+        .iter()
+        .filter(|c| {
+            let diff = (stmt_pos.line() as i64) - (c.0.line() as i64);
+            (diff == 0 || diff == 1) && c.0.end_offset() <= stmt_pos.start_offset()
+        })
+        .filter_map(|c| {
+            if let Comment::CmtBlock(text) = &c.1 {
+                text.trim()
+                    .strip_prefix("HAKANA_ASSERT ")
+                    .map(|assertion| (&c.0, assertion))
+            } else {
+                None
+            }
+        })
+        // loops analyze the same statement more than once, so we remember which
+        // statement each comment belongs to rather than just whether it was used
+        .filter(|(comment_pos, _)| {
+            *analysis_data
+                .inline_assertion_stmts
+                .entry(comment_pos.start_offset() as u32)
+                .or_insert(stmt_start)
+                == stmt_start
+        })
+        .collect::<Vec<_>>();
+
+    for (comment_pos, assertion) in assertions {
+        let (var_id, type_string) = if let Some((var_id, type_string)) = assertion.split_once(':') {
+            (var_id.trim(), type_string.trim())
+        } else {
+            report_invalid_assertion(
+                statements_analyzer,
+                analysis_data,
+                context,
+                comment_pos,
+                format!("Could not parse type assertion {}", assertion),
+            );
+            continue;
+        };
+
+        let existing_type = if let Some(existing_type) = context.locals.get(var_id) {
+            existing_type.clone()
+        } else {
+            report_invalid_assertion(
+                statements_analyzer,
+                analysis_data,
+                context,
+                comment_pos,
+                format!("Cannot assert the type of undefined variable {}", var_id),
+            );
+            continue;
+        };
+
+        let mut asserted_type = if let Some(asserted_type) =
+            get_resolved_type(type_string, statements_analyzer, analysis_data, context)
+        {
+            asserted_type
+        } else {
+            report_invalid_assertion(
+                statements_analyzer,
+                analysis_data,
+                context,
+                comment_pos,
+                format!("Could not resolve type {}", type_string),
+            );
+            continue;
+        };
+
+        let codebase = statements_analyzer.get_codebase();
+
+        // the assertion still wins, but a type that can never match what we inferred
+        // usually means the assertion is stale
+        if !union_type_comparator::can_expression_types_be_identical(
+            codebase,
+            &existing_type,
+            &asserted_type,
+            false,
+        ) {
+            report_invalid_assertion(
+                statements_analyzer,
+                analysis_data,
+                context,
+                comment_pos,
+                format!(
+                    "Asserted type {} for {} is incompatible with inferred type {}",
+                    asserted_type.get_id(Some(statements_analyzer.get_interner())),
+                    var_id,
+                    existing_type.get_id(Some(statements_analyzer.get_interner())),
+                ),
+            );
+        }
+
+        asserted_type.parent_nodes = existing_type.parent_nodes.clone();
+
+        let var_id = var_id.to_string();
+
+        context.remove_var_from_conflicting_clauses(
+            &var_id,
+            Some(&asserted_type),
+            Some(statements_analyzer),
+            analysis_data,
+        );
+
+        context.locals.insert(var_id, Rc::new(asserted_type));
+    }
+}
+
+fn report_invalid_assertion(
+    statements_analyzer: &StatementsAnalyzer,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+    comment_pos: &Pos,
+    description: String,
+) {
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            IssueKind::InvalidInlineTypeAssertion,
+            description,
+            statements_analyzer.get_hpos(comment_pos),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
+
+fn get_resolved_type(
+    type_string: &str,
+    statements_analyzer: &StatementsAnalyzer,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+) -> Option<TUnion> {
+    let mut resolved_type = get_type_from_string(type_string, &mut |name| {
+        resolve_symbol_name(name, statements_analyzer)
+    })?;
+
+    let codebase = statements_analyzer.get_codebase();
+
+    populate_union_type(
+        &mut resolved_type,
+        &codebase.symbols,
+        &context
+            .function_context
+            .get_reference_source(&statements_analyzer.get_file_path().0),
+        &mut analysis_data.symbol_references,
+        false,
+    );
+    type_expander::expand_union(
+        codebase,
+        &Some(statements_analyzer.get_interner()),
+        &mut resolved_type,
+        &TypeExpansionOptions {
+            self_class: context.function_context.calling_class.as_ref(),
+            ..Default::default()
+        },
+        &mut DataFlowGraph::new(GraphKind::FunctionBody),
+    );
+
+    Some(resolved_type)
+}
+
+fn resolve_symbol_name(name: &str, statements_analyzer: &StatementsAnalyzer) -> Option<StrId> {
+    let candidates = if let Some(name) = name.strip_prefix('\\') {
+        vec![name.to_string()]
+    } else if let Some(namespace) = statements_analyzer.get_namespace() {
+        vec![
+            format!("{}\\{}", namespace, name),
+            format!("HH\\{}", name),
+            name.to_string(),
+        ]
+    } else {
+        vec![format!("HH\\{}", name), name.to_string()]
+    };
+
+    let interner = statements_analyzer.get_interner();
+    let codebase = statements_analyzer.get_codebase();
+
+    candidates
+        .iter()
+        .filter_map(|candidate| interner.get(candidate))
+        .find(|symbol| codebase.symbols.all.contains_key(symbol))
+}
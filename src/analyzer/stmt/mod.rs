@@ -9,6 +9,7 @@ pub(crate) mod if_analyzer;
 pub(crate) mod if_conditional_analyzer;
 pub(crate) mod if_conditional_scope;
 pub(crate) mod ifelse_analyzer;
+pub(crate) mod inline_type_assertion_analyzer;
 pub(crate) mod loop_;
 pub(crate) mod loop_analyzer;
 pub(crate) mod return_analyzer;
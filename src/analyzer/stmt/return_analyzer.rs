@@ -264,6 +264,29 @@ pub(crate) fn analyze(
 
             // todo increment non-mixed count
 
+            if expected_return_type.is_nothing() && !inferred_return_type.is_nothing() {
+                analysis_data.maybe_add_issue(
+                    Issue::new(
+                        IssueKind::InvalidReturnStatement,
+                        format!(
+                            "{} is declared noreturn, so it cannot return a value",
+                            context
+                                .function_context
+                                .calling_functionlike_id
+                                .as_ref()
+                                .unwrap()
+                                .to_string(interner)
+                        ),
+                        statements_analyzer.get_hpos(&return_expr.1),
+                        &context.function_context.calling_functionlike_id,
+                    ),
+                    statements_analyzer.get_config(),
+                    statements_analyzer.get_file_path_actual(),
+                );
+
+                return Ok(());
+            }
+
             if expected_return_type.is_void() {
                 analysis_data.maybe_add_issue(
                     Issue::new(
@@ -465,6 +488,25 @@ pub(crate) fn analyze(
                 statements_analyzer.get_file_path_actual());
             }
         }
+    } else if expected_return_type.is_nothing() {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::InvalidReturnStatement,
+                format!(
+                    "{} is declared noreturn, so it cannot return",
+                    context
+                        .function_context
+                        .calling_functionlike_id
+                        .as_ref()
+                        .unwrap()
+                        .to_string(interner)
+                ),
+                statements_analyzer.get_hpos(&stmt.0),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
     } else if !expected_return_type.is_void()
         && !functionlike_storage.has_yield
         && !functionlike_storage.is_async
@@ -1,4 +1,6 @@
 use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_str::{Interner, StrId};
 use hakana_type::{combine_union_types, get_mixed_any};
 use indexmap::IndexMap;
@@ -211,6 +213,8 @@ pub(crate) fn analyze(
         )?;
     }
 
+    check_for_duplicate_cases(statements_analyzer, stmt.1, analysis_data, context);
+
     let mut possibly_redefined_vars = switch_scope.possibly_redefined_vars.unwrap_or_default();
     if let Some(new_locals) = switch_scope.new_locals {
         possibly_redefined_vars.retain(|k, _| !new_locals.contains_key(k));
@@ -248,6 +252,60 @@ pub(crate) fn analyze(
     Ok(())
 }
 
+// Only literal case values are compared, so two cases that reference the same
+// class constant are caught but two unrelated non-literal expressions are not.
+// This only covers switch: match statements are reported as unrecognized by
+// stmt_analyzer, so their arms never get inferred types to compare.
+fn check_for_duplicate_cases(
+    statements_analyzer: &StatementsAnalyzer,
+    cases: &[aast::Case<(), ()>],
+    analysis_data: &mut FunctionAnalysisData,
+    context: &BlockContext,
+) {
+    let mut seen_case_values = FxHashSet::default();
+
+    for case in cases {
+        let case_type = if let Some(case_type) = analysis_data.get_expr_type(case.0.pos()) {
+            case_type
+        } else {
+            continue;
+        };
+
+        if !case_type.is_single() {
+            continue;
+        }
+
+        let case_value = case_type.get_single().clone();
+
+        if !matches!(
+            case_value,
+            TAtomic::TLiteralInt { .. }
+                | TAtomic::TLiteralString { .. }
+                | TAtomic::TEnumLiteralCase { .. }
+        ) {
+            continue;
+        }
+
+        let case_value_id = case_value.get_id(Some(statements_analyzer.get_interner()));
+
+        if !seen_case_values.insert(case_value) {
+            analysis_data.maybe_add_issue(
+                Issue::new(
+                    IssueKind::DuplicateCase,
+                    format!(
+                        "Case value {} has already been handled by a previous case",
+                        case_value_id
+                    ),
+                    statements_analyzer.get_hpos(case.0.pos()),
+                    &context.function_context.calling_functionlike_id,
+                ),
+                statements_analyzer.get_config(),
+                statements_analyzer.get_file_path_actual(),
+            );
+        }
+    }
+}
+
 fn update_case_exit_map(
     codebase: &CodebaseInfo,
     interner: &Interner,
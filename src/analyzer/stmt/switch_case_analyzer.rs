@@ -1,6 +1,7 @@
 use hakana_reflection_info::issue::IssueKind;
 
 use hakana_reflection_info::issue::Issue;
+use hakana_reflection_info::t_atomic::TAtomic;
 
 use hakana_type::combine_union_types;
 
@@ -418,6 +419,26 @@ pub(crate) fn analyze_case(
         }
     }
 
+    if case_cond.is_none()
+        && is_redundant_enum_default(
+            statements_analyzer,
+            switch_var_id,
+            &case_context,
+            original_context,
+        )
+    {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::RedundantDefault,
+                "Every enum case has been handled, so this default is never reached".to_string(),
+                statements_analyzer.get_hpos(case_pos),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
+
     analysis_data.case_scopes.push(CaseScope::new());
 
     statements_analyzer.analyze(&case_stmts, analysis_data, &mut case_context, loop_scope)?;
@@ -527,7 +548,15 @@ pub(crate) fn handle_non_returning_case(
     case_exit_type: &ControlAction,
     switch_scope: &mut SwitchScope,
 ) -> Result<(), AnalysisError> {
-    if is_default_case {
+    // redundant enum defaults have already been reported more specifically
+    if is_default_case
+        && !is_redundant_enum_default(
+            statements_analyzer,
+            switch_var_id,
+            case_context,
+            original_context,
+        )
+    {
         if let Some(switch_type) = case_context.locals.get(switch_var_id) {
             if switch_type.is_nothing() {
                 analysis_data.maybe_add_issue(
@@ -633,3 +662,31 @@ pub(crate) fn handle_non_returning_case(
 
     Ok(())
 }
+
+// Only enabled with find_redundant_defaults, since some teams prefer a defensive
+// default that keeps working when a case is added to the enum
+fn is_redundant_enum_default(
+    statements_analyzer: &StatementsAnalyzer,
+    switch_var_id: &String,
+    case_context: &BlockContext,
+    original_context: &BlockContext,
+) -> bool {
+    if !statements_analyzer.get_config().find_redundant_defaults {
+        return false;
+    }
+
+    if let (Some(switch_type), Some(original_switch_type)) = (
+        case_context.locals.get(switch_var_id),
+        original_context.locals.get(switch_var_id),
+    ) {
+        switch_type.is_nothing()
+            && original_switch_type.types.iter().all(|atomic| {
+                matches!(
+                    atomic,
+                    TAtomic::TEnum { .. } | TAtomic::TEnumLiteralCase { .. }
+                )
+            })
+    } else {
+        false
+    }
+}
@@ -20,7 +20,8 @@ use crate::scope::BlockContext;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt::{
     break_analyzer, continue_analyzer, do_analyzer, for_analyzer, foreach_analyzer,
-    ifelse_analyzer, return_analyzer, switch_analyzer, try_analyzer, while_analyzer,
+    ifelse_analyzer, inline_type_assertion_analyzer, return_analyzer, switch_analyzer,
+    try_analyzer, while_analyzer,
 };
 use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
@@ -68,6 +69,8 @@ pub(crate) fn analyze(
         }
     }
 
+    inline_type_assertion_analyzer::analyze(statements_analyzer, &stmt.0, analysis_data, context);
+
     match &stmt.1 {
         aast::Stmt_::Expr(boxed) => {
             expression_analyzer::analyze(
@@ -3,10 +3,13 @@ use hakana_analyzer::config::{self};
 use hakana_analyzer::custom_hook::CustomHook;
 use hakana_logger::{Logger, Verbosity};
 use hakana_reflection_info::analysis_result::{
-    AnalysisResult, CheckPointEntry, FullEntry, HhClientEntry, Replacement,
+    AnalysisResult, CheckPointEntry, FullEntry, HhClientEntry, JsonArrayWriter, Replacement,
 };
+use hakana_reflection_info::call_graph::CallGraph;
 use hakana_reflection_info::data_flow::graph::{GraphKind, WholeProgramKind};
+use hakana_reflection_info::data_flow::node::DataFlowNodeId;
 use hakana_reflection_info::issue::IssueKind;
+use hakana_reflection_info::mixed_source_profile::MixedSourceProfile;
 use hakana_str::Interner;
 use indexmap::IndexMap;
 use rand::Rng;
@@ -14,7 +17,7 @@ use rustc_hash::FxHashSet;
 use std::collections::BTreeMap;
 use std::env;
 use std::fs::{self, File};
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::process::exit;
 use std::sync::Arc;
@@ -106,11 +109,31 @@ pub fn init(
                             .required(false)
                             .help("Show which functions we lead to mixed types"),
                     )
+                    .arg(
+                        arg!(--"show-mixed-source-profile")
+                            .required(false)
+                            .help("Rank the functions and properties that lead to mixed types"),
+                    )
+                    .arg(
+                        arg!(--"show-type-coverage")
+                            .required(false)
+                            .help("Show how much of each function has non-mixed types"),
+                    )
                     .arg(
                         arg!(--"show-symbol-map")
                             .required(false)
                             .help("Output a map of all symbols"),
                     )
+                    .arg(
+                        arg!(--"output-call-graph" <PATH>)
+                            .required(false)
+                            .help("File to save the static call graph to"),
+                    )
+                    .arg(
+                        arg!(--"call-graph-format" <FORMAT>)
+                            .required(false)
+                            .help("Format for the call graph. Options: json (default), dot"),
+                    )
                     .arg(
                         arg!(--"debug")
                             .required(false)
@@ -1251,6 +1274,16 @@ fn do_security_check(
     }
     config.allowed_issues = None;
 
+    if !config.security_config.enabled {
+        println!(
+            "\nTaint analysis is disabled by disable_taint_analysis in {}\n",
+            config_path.display()
+        );
+        // a security check that silently passes would hide every taint issue in CI
+        *had_error = true;
+        return;
+    }
+
     let output_file = sub_matches.value_of("output").map(|f| f.to_string());
 
     config.security_config.max_depth =
@@ -1323,6 +1356,12 @@ fn do_analysis(
 
     let output_file = sub_matches.value_of("output").map(|f| f.to_string());
     let output_format = sub_matches.value_of("json-format").map(|f| f.to_string());
+    let call_graph_file = sub_matches
+        .value_of("output-call-graph")
+        .map(|f| f.to_string());
+    let call_graph_format = sub_matches
+        .value_of("call-graph-format")
+        .map(|f| f.to_string());
 
     let ignored = sub_matches
         .values_of("ignore")
@@ -1330,6 +1369,8 @@ fn do_analysis(
     let find_unused_expressions = sub_matches.is_present("find-unused-expressions");
     let find_unused_definitions = sub_matches.is_present("find-unused-definitions");
     let show_mixed_function_counts = sub_matches.is_present("show-mixed-function-counts");
+    let show_mixed_source_profile = sub_matches.is_present("show-mixed-source-profile");
+    let show_type_coverage = sub_matches.is_present("show-type-coverage");
     let show_symbol_map = sub_matches.is_present("show-symbol-map");
     let ignore_mixed_issues = sub_matches.is_present("ignore-mixed-issues");
     let show_issue_stats = sub_matches.is_present("show-issue-stats");
@@ -1361,6 +1402,7 @@ fn do_analysis(
     config.find_unused_definitions = find_unused_definitions;
     config.ignore_mixed_issues = ignore_mixed_issues;
     config.ast_diff = do_ast_diff;
+    config.collect_type_coverage = show_type_coverage;
 
     config.hooks = analysis_hooks;
 
@@ -1427,6 +1469,28 @@ fn do_analysis(
             );
         }
 
+        if let Some(call_graph_file) = call_graph_file {
+            let call_graph = CallGraph::new(
+                &analysis_result,
+                &successful_run_data.codebase,
+                &successful_run_data.interner,
+            );
+
+            let call_graph_path = if call_graph_file.starts_with('/') {
+                call_graph_file
+            } else {
+                format!("{}/{}", cwd, call_graph_file)
+            };
+
+            let output = match call_graph_format {
+                Some(format) if format == "dot" => call_graph.to_dot(),
+                _ => call_graph.to_json(),
+            };
+
+            let mut call_graph_output = fs::File::create(Path::new(&call_graph_path)).unwrap();
+            write!(call_graph_output, "{}", output).unwrap();
+        }
+
         if show_issue_stats {
             let mut issues_by_kind = analysis_result
                 .issue_counts
@@ -1447,6 +1511,13 @@ fn do_analysis(
             let mut mixed_sources = analysis_result
                 .mixed_source_counts
                 .iter()
+                // property origins are only included in --show-mixed-source-profile
+                .filter(|(k, _)| {
+                    matches!(
+                        k,
+                        DataFlowNodeId::CallTo(..) | DataFlowNodeId::SpecializedCallTo(..)
+                    )
+                })
                 .map(|(k, v)| {
                     format!(
                         "{}\t{}",
@@ -1460,6 +1531,23 @@ fn do_analysis(
 
             println!("{}", mixed_sources.join("\n"));
         }
+
+        if show_mixed_source_profile {
+            let mixed_source_profile = MixedSourceProfile::new(
+                &analysis_result,
+                &successful_run_data.codebase,
+                &successful_run_data.interner,
+            );
+
+            println!("{}", mixed_source_profile.to_text());
+        }
+
+        if show_type_coverage {
+            println!(
+                "{}",
+                analysis_result.get_type_coverage_text(&successful_run_data.interner)
+            );
+        }
     }
 }
 
@@ -1475,44 +1563,24 @@ fn write_output_files(
     } else {
         format!("{}/{}", cwd, output_file)
     };
-    let mut output_path = fs::File::create(Path::new(&output_path)).unwrap();
-
-    let json = match output_format {
-        Some(format) if format == "full" => {
-            let mut entries = vec![];
-
-            for (file_path, issues) in analysis_result.get_all_issues(interner, cwd, true) {
-                for issue in issues {
-                    entries.push(FullEntry::from_issue(issue, &file_path));
-                }
+    let output_file = fs::File::create(Path::new(&output_path)).unwrap();
+
+    // entries are streamed to the file, since there can be far too many issues
+    // to comfortably hold them all in memory as JSON
+    let mut writer = JsonArrayWriter::new(BufWriter::new(output_file));
+
+    for (file_path, issues) in analysis_result.get_issues_by_file(interner, cwd, true) {
+        for issue in issues {
+            match output_format.as_deref() {
+                Some("full") => writer.push(&FullEntry::from_issue(issue, &file_path)),
+                Some("hh_client") => writer.push(&HhClientEntry::from_issue(issue, &file_path)),
+                _ => writer.push(&CheckPointEntry::from_issue(issue, &file_path)),
             }
-
-            serde_json::to_string_pretty(&entries).unwrap()
-        }
-        Some(format) if format == "hh_client" => {
-            let mut entries = vec![];
-
-            for (file_path, issues) in analysis_result.get_all_issues(interner, cwd, true) {
-                for issue in issues {
-                    entries.push(HhClientEntry::from_issue(issue, &file_path));
-                }
-            }
-
-            serde_json::to_string_pretty(&entries).unwrap()
+            .unwrap();
         }
-        _ => {
-            let mut checkpoint_entries = vec![];
-
-            for (file_path, issues) in analysis_result.get_all_issues(interner, cwd, true) {
-                for issue in issues {
-                    checkpoint_entries.push(CheckPointEntry::from_issue(issue, &file_path));
-                }
-            }
+    }
 
-            serde_json::to_string_pretty(&checkpoint_entries).unwrap()
-        }
-    };
-    write!(output_path, "{}", json).unwrap();
+    writer.finish().unwrap();
 }
 
 fn update_files(analysis_result: &mut AnalysisResult, root_dir: &String, interner: &Interner) {
@@ -1,12 +1,55 @@
+use hakana_analyzer::custom_hook::{CustomHook, InternalHook, TypeHintHook};
+use hakana_reflection_info::{t_atomic::TAtomic, t_union::TUnion};
+use hakana_str::ThreadedInterner;
+
 use super::test_runner::HooksProvider;
 
 pub struct CoreHooksProvider {}
 
 impl HooksProvider for CoreHooksProvider {
-    fn get_hooks_for_test(
-        &self,
-        _: &str,
-    ) -> Vec<Box<dyn hakana_analyzer::custom_hook::CustomHook>> {
+    fn get_hooks_for_test(&self, dir: &str) -> Vec<Box<dyn CustomHook>> {
+        if dir.contains("typeHintHook") {
+            return vec![Box::new(LegacyMixedHintHook {})];
+        }
+
         vec![]
     }
 }
+
+// Treats the test-only LegacyMixed alias as a string wherever it's used in a signature,
+// and erases the element type of every vec hint
+#[derive(Debug)]
+struct LegacyMixedHintHook {}
+
+impl TypeHintHook for LegacyMixedHintHook {
+    fn transform_type_hint(
+        &self,
+        hint_type: &TUnion,
+        interner: &mut ThreadedInterner,
+    ) -> Option<TUnion> {
+        if !hint_type.is_single() {
+            return None;
+        }
+
+        match hint_type.get_single() {
+            TAtomic::TReference { name, .. } if interner.lookup(*name) == "LegacyMixed" => {
+                Some(TUnion::new(vec![TAtomic::TString]))
+            }
+            TAtomic::TVec { .. } => Some(TUnion::new(vec![TAtomic::TVec {
+                known_items: None,
+                type_param: Box::new(TUnion::new(vec![TAtomic::TMixed])),
+                known_count: None,
+                non_empty: false,
+            }])),
+            _ => None,
+        }
+    }
+}
+
+impl InternalHook for LegacyMixedHintHook {
+    fn get_type_hint_hook(&self) -> Option<&dyn TypeHintHook> {
+        Some(self)
+    }
+}
+
+impl CustomHook for LegacyMixedHintHook {}
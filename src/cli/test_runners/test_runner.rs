@@ -2,10 +2,13 @@ use hakana_analyzer::config;
 use hakana_analyzer::custom_hook::CustomHook;
 use hakana_logger::Logger;
 use hakana_reflection_info::analysis_result::AnalysisResult;
+use hakana_reflection_info::call_graph::CallGraph;
 use hakana_reflection_info::code_location::FilePath;
 use hakana_reflection_info::data_flow::graph::GraphKind;
 use hakana_reflection_info::data_flow::graph::WholeProgramKind;
+use hakana_reflection_info::diff::get_affected_files;
 use hakana_reflection_info::issue::IssueKind;
+use hakana_reflection_info::mixed_source_profile::MixedSourceProfile;
 use hakana_str::Interner;
 use hakana_workhorse::wasm::get_single_file_codebase;
 use hakana_workhorse::SuccessfulScanData;
@@ -20,6 +23,7 @@ use std::io;
 use std::io::Write;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use walkdir::WalkDir;
 
@@ -135,6 +139,20 @@ impl TestRunner {
             || dir.contains("UnusedClosureParameter");
         analysis_config.find_unused_definitions =
             dir.to_ascii_lowercase().contains("unused") && !dir.contains("UnusedExpression");
+        analysis_config.strict_string_concatenation = dir.contains("StrictConcat");
+        analysis_config.find_invalid_concat_operands = dir.contains("InvalidConcatOperand");
+        analysis_config.find_redundant_boolean_comparisons =
+            dir.contains("RedundantBooleanComparison");
+        analysis_config.find_redundant_defaults = dir.contains("RedundantDefault");
+        analysis_config.find_redundant_casts = dir.contains("RedundantCast");
+        analysis_config.report_unresolved_templates = dir.contains("UnresolvedTemplate");
+        analysis_config.find_missing_override_attributes = dir.contains("MissingOverrideAttribute");
+        analysis_config.require_override_for_interface_methods =
+            dir.contains("MissingOverrideAttribute/Interface");
+        analysis_config.find_unused_method_parameters = dir.contains("UnusedMethodParameter");
+        analysis_config.find_unsafe_instantiations = dir.contains("UnsafeInstantiation");
+        analysis_config.widen_loop_isset_mixed = dir.contains("WidenLoopIssetMixed");
+        analysis_config.collect_type_coverage = dir.contains("TypeCoverage");
         analysis_config.graph_kind = if dir.contains("/security/") {
             GraphKind::WholeProgram(WholeProgramKind::Taint)
         } else if dir.contains("/find-paths/") {
@@ -364,6 +382,13 @@ impl TestRunner {
                 Ok((analysis_result, run_data)) => {
                     *total_time_in_analysis += analysis_result.time_in_analysis;
 
+                    if let Some(diagnostic) =
+                        get_report_diagnostic(&dir, &analysis_result, &run_data)
+                    {
+                        test_diagnostics.push((dir, diagnostic));
+                        return ("F".to_string(), Some(run_data), Some(analysis_result));
+                    }
+
                     let mut output = vec![];
                     for (file_path, issues) in
                         analysis_result.get_all_issues(&run_data.interner, &dir, true)
@@ -467,9 +492,15 @@ impl TestRunner {
         let mut previous_scan_data = None;
         let mut previous_analysis_result = None;
 
+        let streamed_issues = Arc::new(Mutex::new(vec![]));
+
         let mut config = self.get_config_for_test(&workdir_base);
         config.ast_diff = true;
         config.find_unused_definitions = true;
+        config.issue_callback = Some(config::IssueCallback::new({
+            let streamed_issues = streamed_issues.clone();
+            move |issue| streamed_issues.lock().unwrap().push(issue.clone())
+        }));
         let interner = Interner::default();
         let config = Arc::new(config);
         let mut stub_dirs = vec![cwd.clone() + "/tests/stubs"];
@@ -481,6 +512,8 @@ impl TestRunner {
         for (folder, change_after_scan) in folders {
             copy_recursively(folder.clone(), workdir_base.clone()).unwrap();
 
+            streamed_issues.lock().unwrap().clear();
+
             let run_result = hakana_workhorse::scan_and_analyze(
                 stub_dirs.clone(),
                 None,
@@ -523,17 +556,73 @@ impl TestRunner {
         let run_data = previous_scan_data.unwrap();
         let analysis_result = previous_analysis_result.unwrap();
 
+        let streamed_issues = streamed_issues.lock().unwrap();
+
         let mut output = vec![];
+        let mut unstreamed_output = vec![];
         for (file_path, issues) in
             analysis_result.get_all_issues(&run_data.interner, &workdir_base, true)
         {
             for issue in issues {
+                // issues loaded from the cache need to reach the callback too
+                if !streamed_issues.contains(issue) {
+                    unstreamed_output.push(issue.format(&file_path));
+                }
+
                 output.push(issue.format(&file_path));
             }
         }
 
+        if !unstreamed_output.is_empty() {
+            test_diagnostics.push((
+                dir,
+                format!(
+                    "Issues not passed to the issue callback:\n{}",
+                    unstreamed_output.join("")
+                ),
+            ));
+            return ("F".to_string(), Some(run_data), Some(analysis_result));
+        }
+
         let test_output = output;
 
+        // the first line names a symbol, and the rest list the files affected by changing it
+        let affected_files_path = dir.clone() + "/affected_files.txt";
+        if Path::new(&affected_files_path).exists() {
+            let affected_files_contents = fs::read_to_string(affected_files_path).unwrap();
+            let mut lines = affected_files_contents.trim().lines();
+            let symbol_name = lines.next().unwrap_or_default();
+            let expected_affected_files = lines.collect::<Vec<_>>().join("\n");
+
+            let mut affected_files = if let Some(symbol) = run_data.interner.get(symbol_name) {
+                get_affected_files(
+                    &symbol,
+                    &analysis_result.symbol_references,
+                    &run_data.codebase,
+                )
+                .into_iter()
+                .map(|file_path| {
+                    FilePath(file_path).get_relative_path(&run_data.interner, &workdir_base)
+                })
+                .collect::<Vec<_>>()
+            } else {
+                vec![]
+            };
+            affected_files.sort();
+
+            if affected_files.join("\n") != expected_affected_files {
+                test_diagnostics.push((
+                    dir,
+                    format!(
+                        "- {}\n+ {}",
+                        expected_affected_files,
+                        affected_files.join("\n")
+                    ),
+                ));
+                return ("F".to_string(), Some(run_data), Some(analysis_result));
+            }
+        }
+
         let expected_output_path = dir.clone() + "/output.txt";
         let expected_output = if Path::new(&expected_output_path).exists() {
             let expected = fs::read_to_string(expected_output_path)
@@ -577,6 +666,61 @@ impl TestRunner {
     }
 }
 
+// A test can check the output of a report as well as its issues by adding a
+// file named after the report
+fn get_report_diagnostic(
+    dir: &str,
+    analysis_result: &AnalysisResult,
+    run_data: &SuccessfulScanData,
+) -> Option<String> {
+    let mixed_source_profile_path = format!("{}/mixed_source_profile.txt", dir);
+    if Path::new(&mixed_source_profile_path).exists() {
+        let expected_profile = fs::read_to_string(mixed_source_profile_path).unwrap();
+        let profile =
+            MixedSourceProfile::new(analysis_result, &run_data.codebase, &run_data.interner)
+                .to_text();
+
+        if expected_profile.trim() != profile.trim() {
+            return Some(format!(
+                "- {}\n+ {}",
+                expected_profile.trim(),
+                profile.trim()
+            ));
+        }
+    }
+
+    let type_coverage_path = format!("{}/type_coverage.txt", dir);
+    if Path::new(&type_coverage_path).exists() {
+        let expected_coverage = fs::read_to_string(type_coverage_path).unwrap();
+        let coverage = analysis_result.get_type_coverage_text(&run_data.interner);
+
+        if expected_coverage.trim() != coverage.trim() {
+            return Some(format!(
+                "- {}\n+ {}",
+                expected_coverage.trim(),
+                coverage.trim()
+            ));
+        }
+    }
+
+    let call_graph_path = format!("{}/call_graph.dot", dir);
+    if Path::new(&call_graph_path).exists() {
+        let expected_call_graph = fs::read_to_string(call_graph_path).unwrap();
+        let call_graph =
+            CallGraph::new(analysis_result, &run_data.codebase, &run_data.interner).to_dot();
+
+        if expected_call_graph.trim() != call_graph.trim() {
+            return Some(format!(
+                "- {}\n+ {}",
+                expected_call_graph.trim(),
+                call_graph.trim()
+            ));
+        }
+    }
+
+    None
+}
+
 fn copy_recursively(source: impl AsRef<Path>, destination: impl AsRef<Path>) -> io::Result<()> {
     fs::create_dir_all(&destination)?;
     for entry in fs::read_dir(source)? {
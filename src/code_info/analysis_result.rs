@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, time::Duration};
+use std::{collections::BTreeMap, io, io::Write, time::Duration};
 
 use hakana_str::Interner;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -24,6 +24,28 @@ pub enum Replacement {
     Substitute(String),
 }
 
+/// How many of a function's expressions, params and return type have a type
+/// that's not mixed
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TypeCoverage {
+    pub typed_count: usize,
+    pub total_count: usize,
+}
+
+impl TypeCoverage {
+    pub fn get_grade(&self) -> f64 {
+        if self.total_count == 0 {
+            1.0
+        } else {
+            self.typed_count as f64 / self.total_count as f64
+        }
+    }
+
+    pub fn get_untyped_count(&self) -> usize {
+        self.total_count - self.typed_count
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AnalysisResult {
     pub emitted_issues: FxHashMap<FilePath, Vec<Issue>>,
@@ -37,6 +59,8 @@ pub struct AnalysisResult {
     pub issue_counts: FxHashMap<IssueKind, usize>,
     pub time_in_analysis: Duration,
     pub functions_to_migrate: FxHashMap<FunctionLikeIdentifier, bool>,
+    pub type_coverage: FxHashMap<FunctionLikeIdentifier, TypeCoverage>,
+    pub functions_with_dynamic_calls: FxHashSet<FunctionLikeIdentifier>,
     pub has_invalid_hack_files: bool,
     pub changed_during_analysis_files: FxHashSet<FilePath>,
 }
@@ -57,6 +81,8 @@ impl AnalysisResult {
             symbol_references,
             time_in_analysis: Duration::default(),
             functions_to_migrate: FxHashMap::default(),
+            type_coverage: FxHashMap::default(),
+            functions_with_dynamic_calls: FxHashSet::default(),
             codegen: BTreeMap::default(),
             has_invalid_hack_files: false,
             changed_during_analysis_files: FxHashSet::default(),
@@ -82,11 +108,59 @@ impl AnalysisResult {
             *self.issue_counts.entry(kind).or_insert(0) += count;
         }
         self.functions_to_migrate.extend(other.functions_to_migrate);
+        self.type_coverage.extend(other.type_coverage);
+        self.functions_with_dynamic_calls
+            .extend(other.functions_with_dynamic_calls);
         self.codegen.extend(other.codegen);
         self.changed_during_analysis_files.extend(other.changed_during_analysis_files);
         self.has_invalid_hack_files = self.has_invalid_hack_files || other.has_invalid_hack_files;
     }
 
+    pub fn get_type_coverage_text(&self, interner: &Interner) -> String {
+        let mut type_coverage = self
+            .type_coverage
+            .iter()
+            .map(|(functionlike_id, coverage)| (functionlike_id.to_string(interner), coverage))
+            .collect::<Vec<_>>();
+
+        // functions with the most untyped expressions first, so that trivial
+        // functions with a poor grade don't crowd out the ones worth fixing
+        type_coverage.sort_by(|(a_name, a), (b_name, b)| {
+            b.get_untyped_count()
+                .cmp(&a.get_untyped_count())
+                .then_with(|| a_name.cmp(b_name))
+        });
+
+        let mut lines = vec![];
+
+        let mut typed_count = 0;
+        let mut total_count = 0;
+
+        for (functionlike_name, coverage) in type_coverage {
+            lines.push(format!(
+                "{}\t{:.1}%\t{}/{}",
+                functionlike_name,
+                coverage.get_grade() * 100.0,
+                coverage.typed_count,
+                coverage.total_count
+            ));
+
+            typed_count += coverage.typed_count;
+            total_count += coverage.total_count;
+        }
+
+        if total_count > 0 {
+            lines.push(format!(
+                "\nOverall type coverage: {:.1}% ({}/{})",
+                typed_count as f64 / total_count as f64 * 100.0,
+                typed_count,
+                total_count
+            ));
+        }
+
+        lines.join("\n")
+    }
+
     pub fn get_all_issues(
         &self,
         interner: &Interner,
@@ -132,6 +206,50 @@ impl AnalysisResult {
 
         issues
     }
+
+    /// The same issues as `get_all_issues`, in the same order, but only one file's
+    /// issues are collected at a time
+    pub fn get_issues_by_file<'a>(
+        &'a self,
+        interner: &'a Interner,
+        root_dir: &'a str,
+        use_relative_path: bool,
+    ) -> impl Iterator<Item = (String, Vec<&'a Issue>)> + 'a {
+        let file_paths = self
+            .emitted_issues
+            .iter()
+            .chain(self.emitted_definition_issues.iter())
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(k, _)| {
+                (
+                    if use_relative_path {
+                        k.get_relative_path(interner, root_dir)
+                    } else {
+                        interner.lookup(&k.0).to_string()
+                    },
+                    *k,
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        file_paths.into_iter().map(move |(file_name, file_path)| {
+            let mut file_issues = self
+                .emitted_issues
+                .get(&file_path)
+                .into_iter()
+                .flatten()
+                .chain(
+                    self.emitted_definition_issues
+                        .get(&file_path)
+                        .into_iter()
+                        .flatten(),
+                )
+                .collect::<Vec<_>>();
+            file_issues.sort_by(|a, b| a.pos.start_offset.cmp(&b.pos.start_offset));
+
+            (file_name, file_issues)
+        })
+    }
 }
 
 #[derive(Serialize)]
@@ -206,3 +324,102 @@ impl HhClientEntry {
         }
     }
 }
+
+/// Writes a pretty-printed JSON array one entry at a time, so that large issue
+/// sets don't need to be collected and serialized all at once. The output
+/// matches `serde_json::to_string_pretty` of the equivalent Vec.
+pub struct JsonArrayWriter<W: Write> {
+    writer: W,
+    has_entries: bool,
+}
+
+impl<W: Write> JsonArrayWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            has_entries: false,
+        }
+    }
+
+    pub fn push<T: Serialize>(&mut self, entry: &T) -> io::Result<()> {
+        // the opening bracket is deferred so that an empty array is written as []
+        self.writer
+            .write_all(if self.has_entries { b",\n" } else { b"[\n" })?;
+        self.has_entries = true;
+
+        let json = serde_json::to_string_pretty(entry)?;
+
+        for (i, line) in json.lines().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b"\n")?;
+            }
+
+            write!(self.writer, "  {}", line)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer
+            .write_all(if self.has_entries { b"\n]" } else { b"[]" })?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonArrayWriter;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Entry {
+        name: String,
+        values: Vec<u32>,
+    }
+
+    fn assert_matches_to_string_pretty(entries: Vec<Entry>) {
+        let mut writer = JsonArrayWriter::new(vec![]);
+
+        for entry in &entries {
+            writer.push(entry).unwrap();
+        }
+
+        let streamed = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(streamed, serde_json::to_string_pretty(&entries).unwrap());
+    }
+
+    #[test]
+    fn json_array_writer_empty() {
+        assert_matches_to_string_pretty(vec![]);
+    }
+
+    #[test]
+    fn json_array_writer_single_entry() {
+        assert_matches_to_string_pretty(vec![Entry {
+            name: "a".to_string(),
+            values: vec![1, 2],
+        }]);
+    }
+
+    #[test]
+    fn json_array_writer_multiple_entries() {
+        assert_matches_to_string_pretty(vec![
+            Entry {
+                name: "a".to_string(),
+                values: vec![1, 2],
+            },
+            Entry {
+                name: "b\n\"c\"".to_string(),
+                values: vec![],
+            },
+            Entry {
+                name: "d".to_string(),
+                values: vec![3],
+            },
+        ]);
+    }
+}
@@ -1,7 +1,7 @@
 use std::hash::Hasher;
 
 use derivative::Derivative;
-use hakana_str::Interner;
+use hakana_str::{Interner, StrId};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -39,10 +39,43 @@ pub enum Assertion {
     EmptyCountable,
     HasExactCount(usize),
     DoesNotHaveExactCount(usize),
+    IsList,
+    IsNotList,
+    IsCallable,
+    IsNotCallable,
+    HasSubstring(SubstringPosition, String),
+    DoesNotHaveSubstring(SubstringPosition, String),
     IgnoreTaints,
     DontIgnoreTaints,
     RemoveTaints(VarId, #[derivative(Hash = "ignore")] Vec<SinkType>),
     DontRemoveTaints(VarId, #[derivative(Hash = "ignore")] Vec<SinkType>),
+    FunctionExists(StrId),
+    FunctionDoesNotExist(StrId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SubstringPosition {
+    Anywhere,
+    Start,
+    End,
+}
+
+impl SubstringPosition {
+    pub fn matches(&self, haystack: &str, needle: &str) -> bool {
+        match self {
+            SubstringPosition::Anywhere => haystack.contains(needle),
+            SubstringPosition::Start => haystack.starts_with(needle),
+            SubstringPosition::End => haystack.ends_with(needle),
+        }
+    }
+
+    fn get_name(self) -> &'static str {
+        match self {
+            SubstringPosition::Anywhere => "contains",
+            SubstringPosition::Start => "starts-with",
+            SubstringPosition::End => "ends-with",
+        }
+    }
 }
 
 impl Assertion {
@@ -88,6 +121,16 @@ impl Assertion {
             Assertion::DoesNotHaveExactCount(number) => {
                 "!has-exactly-".to_string() + &number.to_string()
             }
+            Assertion::IsList => "list".to_string(),
+            Assertion::IsNotList => "!list".to_string(),
+            Assertion::IsCallable => "callable".to_string(),
+            Assertion::IsNotCallable => "!callable".to_string(),
+            Assertion::HasSubstring(position, substring) => {
+                format!("{}-'{}'", position.get_name(), substring)
+            }
+            Assertion::DoesNotHaveSubstring(position, substring) => {
+                format!("!{}-'{}'", position.get_name(), substring)
+            }
             Assertion::IgnoreTaints => "ignore-taints".to_string(),
             Assertion::DontIgnoreTaints => "dont-ignore-taints".to_string(),
             Assertion::RemoveTaints(key, _) => {
@@ -104,6 +147,20 @@ impl Assertion {
                     format!("!remove-some-taints-{}", key.0 .0)
                 }
             }
+            Assertion::FunctionExists(name) => {
+                if let Some(interner) = interner {
+                    format!("function-exists-{}", interner.lookup(name))
+                } else {
+                    format!("function-exists-{}", name.0)
+                }
+            }
+            Assertion::FunctionDoesNotExist(name) => {
+                if let Some(interner) = interner {
+                    format!("!function-exists-{}", interner.lookup(name))
+                } else {
+                    format!("!function-exists-{}", name.0)
+                }
+            }
         }
     }
 
@@ -126,6 +183,9 @@ impl Assertion {
                 | Assertion::DoesNotHaveExactCount(_)
                 | Assertion::DoesNotHaveNonnullEntryForKey(_)
                 | Assertion::EmptyCountable
+                | Assertion::IsNotList
+                | Assertion::IsNotCallable
+                | Assertion::DoesNotHaveSubstring(..)
         )
     }
 
@@ -258,6 +318,22 @@ impl Assertion {
                 Assertion::HasExactCount(other_number) => other_number == number,
                 _ => false,
             },
+            Assertion::IsList => matches!(other, Assertion::IsNotList),
+            Assertion::IsNotList => matches!(other, Assertion::IsList),
+            Assertion::IsCallable => matches!(other, Assertion::IsNotCallable),
+            Assertion::IsNotCallable => matches!(other, Assertion::IsCallable),
+            Assertion::HasSubstring(position, substring) => match other {
+                Assertion::DoesNotHaveSubstring(other_position, other_substring) => {
+                    other_position == position && other_substring == substring
+                }
+                _ => false,
+            },
+            Assertion::DoesNotHaveSubstring(position, substring) => match other {
+                Assertion::HasSubstring(other_position, other_substring) => {
+                    other_position == position && other_substring == substring
+                }
+                _ => false,
+            },
             Assertion::IgnoreTaints => matches!(other, Assertion::DontIgnoreTaints),
             Assertion::DontIgnoreTaints => matches!(other, Assertion::IgnoreTaints),
             Assertion::RemoveTaints(key, taints) => match other {
@@ -272,6 +348,14 @@ impl Assertion {
                 }
                 _ => false,
             },
+            Assertion::FunctionExists(name) => match other {
+                Assertion::FunctionDoesNotExist(other_name) => other_name == name,
+                _ => false,
+            },
+            Assertion::FunctionDoesNotExist(name) => match other {
+                Assertion::FunctionExists(other_name) => other_name == name,
+                _ => false,
+            },
         }
     }
 
@@ -300,6 +384,16 @@ impl Assertion {
             Assertion::NotInArray(union) => Assertion::InArray(union.clone()),
             Assertion::HasExactCount(size) => Assertion::DoesNotHaveExactCount(*size),
             Assertion::DoesNotHaveExactCount(size) => Assertion::HasExactCount(*size),
+            Assertion::IsList => Assertion::IsNotList,
+            Assertion::IsNotList => Assertion::IsList,
+            Assertion::IsCallable => Assertion::IsNotCallable,
+            Assertion::IsNotCallable => Assertion::IsCallable,
+            Assertion::HasSubstring(position, substring) => {
+                Assertion::DoesNotHaveSubstring(*position, substring.clone())
+            }
+            Assertion::DoesNotHaveSubstring(position, substring) => {
+                Assertion::HasSubstring(*position, substring.clone())
+            }
             Assertion::HasArrayKey(str) => Assertion::DoesNotHaveArrayKey(str.clone()),
             Assertion::DoesNotHaveArrayKey(str) => Assertion::HasArrayKey(str.clone()),
             Assertion::HasNonnullEntryForKey(str) => {
@@ -322,6 +416,8 @@ impl Assertion {
             Assertion::DontRemoveTaints(key, taints) => {
                 Assertion::RemoveTaints(*key, taints.clone())
             }
+            Assertion::FunctionExists(name) => Assertion::FunctionDoesNotExist(*name),
+            Assertion::FunctionDoesNotExist(name) => Assertion::FunctionExists(*name),
         }
     }
 }
@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hakana_str::{Interner, StrId};
+use serde::Serialize;
+
+use crate::{
+    analysis_result::AnalysisResult, codebase_info::CodebaseInfo,
+    functionlike_identifier::FunctionLikeIdentifier,
+};
+
+/// A static call graph, built from the references recorded during analysis
+#[derive(Debug, Default, Serialize)]
+pub struct CallGraph {
+    // A map of every function and method to the functions and methods it may call.
+    // Method calls include every override of the called method, since we can't
+    // tell statically which one will be dispatched to.
+    pub calls: BTreeMap<String, BTreeSet<String>>,
+
+    // Functions and methods that also make calls we cannot resolve, e.g. `$f()`
+    // or `$obj->$method()`
+    pub unresolved: BTreeSet<String>,
+}
+
+impl CallGraph {
+    pub fn new(
+        analysis_result: &AnalysisResult,
+        codebase: &CodebaseInfo,
+        interner: &Interner,
+    ) -> Self {
+        let mut call_graph = CallGraph::default();
+
+        for (caller, callees) in &analysis_result
+            .symbol_references
+            .symbol_references_to_symbols
+        {
+            if !codebase.functionlike_infos.contains_key(caller) {
+                continue;
+            }
+
+            let mut targets = BTreeSet::new();
+
+            for callee in callees {
+                if !codebase.functionlike_infos.contains_key(callee) {
+                    continue;
+                }
+
+                targets.insert(get_functionlike_name(callee, interner));
+
+                if callee.1 != StrId::EMPTY {
+                    for descendant in codebase.get_all_descendants(&callee.0) {
+                        if codebase
+                            .functionlike_infos
+                            .contains_key(&(descendant, callee.1))
+                        {
+                            targets
+                                .insert(get_functionlike_name(&(descendant, callee.1), interner));
+                        }
+                    }
+                }
+            }
+
+            if !targets.is_empty() {
+                call_graph
+                    .calls
+                    .entry(get_functionlike_name(caller, interner))
+                    .or_default()
+                    .extend(targets);
+            }
+        }
+
+        for functionlike_id in &analysis_result.functions_with_dynamic_calls {
+            call_graph
+                .unresolved
+                .insert(functionlike_id.to_string(interner));
+        }
+
+        call_graph
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec!["digraph calls {".to_string()];
+
+        for (caller, callees) in &self.calls {
+            for callee in callees {
+                lines.push(format!(
+                    "    \"{}\" -> \"{}\";",
+                    escape_dot_id(caller),
+                    escape_dot_id(callee)
+                ));
+            }
+        }
+
+        for caller in &self.unresolved {
+            lines.push(format!(
+                "    \"{}\" -> \"<unresolved>\" [style=dashed];",
+                escape_dot_id(caller)
+            ));
+        }
+
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+}
+
+fn get_functionlike_name(functionlike_ref: &(StrId, StrId), interner: &Interner) -> String {
+    if functionlike_ref.1 == StrId::EMPTY {
+        FunctionLikeIdentifier::Function(functionlike_ref.0).to_string(interner)
+    } else {
+        FunctionLikeIdentifier::Method(functionlike_ref.0, functionlike_ref.1).to_string(interner)
+    }
+}
+
+fn escape_dot_id(id: &str) -> String {
+    id.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -1,3 +1,4 @@
+use hakana_str::StrId;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -16,5 +17,9 @@ pub struct ConstantInfo {
 
     pub unresolved_value: Option<UnresolvedConstantComponent>,
 
+    // For global constants defined as another global constant (`const B = A;`
+    // or `define('B', A)`), which may be declared in a different file
+    pub aliased_constant: Option<StrId>,
+
     pub is_abstract: bool,
 }
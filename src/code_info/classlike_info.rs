@@ -37,6 +37,8 @@ pub struct ClassLikeInfo {
 
     pub is_deprecated: bool,
 
+    pub deprecation_message: Option<StrId>,
+
     pub internal_to: Option<String>,
 
     pub name: StrId,
@@ -189,6 +191,8 @@ pub struct ClassLikeInfo {
 
     pub enum_type: Option<TAtomic>,
     pub enum_constraint: Option<Box<TAtomic>>,
+    // enums whose cases are included via `use`, e.g. `enum B: int { use A; }`
+    pub enum_includes: Vec<StrId>,
 
     pub type_constants: FxHashMap<StrId, ClassConstantType>,
 
@@ -219,6 +223,7 @@ impl ClassLikeInfo {
             is_populated: false,
             is_stubbed: false,
             is_deprecated: false,
+            deprecation_message: None,
             is_abstract: false,
             is_final: false,
             kind: SymbolKind::Class,
@@ -243,6 +248,7 @@ impl ClassLikeInfo {
             inheritable_method_ids: FxHashMap::default(),
             enum_type: None,
             enum_constraint: None,
+            enum_includes: vec![],
             hash: None,
             inheritable_property_ids: FxHashMap::default(),
             initialized_properties: vec![],
@@ -248,8 +248,10 @@ impl DataFlowGraph {
         let origin_node_ids = self.get_origin_node_ids(&assignment_node.id, &[], false);
 
         for origin_node_id in origin_node_ids {
-            if let DataFlowNodeId::CallTo(..) | DataFlowNodeId::SpecializedCallTo(..) =
-                origin_node_id
+            if let DataFlowNodeId::CallTo(..)
+            | DataFlowNodeId::SpecializedCallTo(..)
+            | DataFlowNodeId::Property(..)
+            | DataFlowNodeId::SpecializedProperty(..) = origin_node_id
             {
                 if let Some(entry) = self.mixed_source_counts.get_mut(&origin_node_id) {
                     entry.insert(pos.to_string());
@@ -1,7 +1,9 @@
 use hakana_str::StrId;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::code_location::FilePath;
+use crate::{
+    code_location::FilePath, codebase_info::CodebaseInfo, symbol_references::SymbolReferences,
+};
 
 #[derive(Default, Debug)]
 pub struct CodebaseDiff {
@@ -21,3 +23,55 @@ impl CodebaseDiff {
         self.deletion_ranges_map.extend(other.deletion_ranges_map);
     }
 }
+
+/// Returns every file that contains the given symbol or something that transitively
+/// references it, i.e. the files that may need to be reanalyzed when it changes
+pub fn get_affected_files(
+    symbol: &StrId,
+    references: &SymbolReferences,
+    codebase: &CodebaseInfo,
+) -> FxHashSet<StrId> {
+    let back_references = references.back_references();
+
+    let mut symbol_files = FxHashMap::default();
+
+    for (file_path, file_info) in &codebase.files {
+        for node in &file_info.ast_nodes {
+            symbol_files.insert(node.name, file_path.0);
+        }
+    }
+
+    let mut seen_symbols = FxHashSet::default();
+
+    // members of the symbol are referenced separately from the symbol itself
+    let mut new_symbols = back_references
+        .keys()
+        .filter(|referenced_symbol| &referenced_symbol.0 == symbol)
+        .copied()
+        .collect::<Vec<_>>();
+
+    new_symbols.push((*symbol, StrId::EMPTY));
+
+    while let Some(new_symbol) = new_symbols.pop() {
+        if !seen_symbols.insert(new_symbol) {
+            continue;
+        }
+
+        if let Some(referencing_symbols) = back_references.get(&new_symbol) {
+            new_symbols.extend(referencing_symbols.iter().copied());
+        }
+    }
+
+    let mut affected_files = FxHashSet::default();
+
+    for (symbol_name, _) in seen_symbols {
+        if let Some(file_path) = symbol_files.get(&symbol_name) {
+            affected_files.insert(*file_path);
+        } else if codebase.files.contains_key(&FilePath(symbol_name)) {
+            // references from top-level code are recorded against the file itself
+            affected_files.insert(symbol_name);
+        }
+    }
+
+    affected_files
+}
@@ -75,4 +75,30 @@ impl FunctionContext {
             }
         }
     }
+
+    /// Deprecated code can use other deprecated code without complaint
+    pub fn is_deprecated(&self, codebase: &CodebaseInfo) -> bool {
+        let functionlike_info = match self.calling_functionlike_id {
+            Some(FunctionLikeIdentifier::Function(function_id)) => codebase
+                .functionlike_infos
+                .get(&(function_id, StrId::EMPTY)),
+            Some(FunctionLikeIdentifier::Method(classlike_name, method_name)) => codebase
+                .functionlike_infos
+                .get(&(classlike_name, method_name)),
+            _ => None,
+        };
+
+        if functionlike_info.map_or(false, |functionlike_info| functionlike_info.deprecated) {
+            return true;
+        }
+
+        if let Some(calling_class) = self.calling_class {
+            codebase
+                .classlike_infos
+                .get(&calling_class)
+                .map_or(false, |classlike_info| classlike_info.is_deprecated)
+        } else {
+            false
+        }
+    }
 }
@@ -84,6 +84,9 @@ pub struct FunctionLikeInfo {
     /// Whether this function is deprecated
     pub deprecated: bool,
 
+    /// The message given to `<<__Deprecated>>`, if any
+    pub deprecation_message: Option<StrId>,
+
     /**
     An array holding the class template "as" types.
 
@@ -128,6 +131,11 @@ pub struct FunctionLikeInfo {
     */
     pub ignore_taints_if_true: bool,
 
+    /**
+    If this is given the function's return value never carries taints, whatever it's called with
+    */
+    pub safe_return: bool,
+
     pub taint_source_types: Vec<SourceType>,
 
     pub added_taints: Vec<SinkType>,
@@ -144,6 +152,9 @@ pub struct FunctionLikeInfo {
     /// used for dead-code analysis — this is true for all __EntryPoint and __DynamicallyCallable functions
     pub dynamically_callable: bool,
 
+    /// <<__EntryPoint>> functions are where request (or command-line) data enters the program
+    pub is_entry_point: bool,
+
     /// generated functions also get a pass
     pub generated: bool,
 
@@ -181,6 +192,7 @@ impl FunctionLikeInfo {
             user_defined: false,
             suppressed_issues: vec![],
             deprecated: false,
+            deprecation_message: None,
             template_types: vec![],
             has_visitor_issues: false,
             has_yield: false,
@@ -197,8 +209,10 @@ impl FunctionLikeInfo {
             must_use: false,
             ignore_taint_path: false,
             dynamically_callable: false,
+            is_entry_point: false,
             generated: false,
             ignore_taints_if_true: false,
+            safe_return: false,
             type_resolution_context: None,
             where_constraints: vec![],
             async_version: None,
@@ -42,6 +42,12 @@ pub struct FunctionLikeParameter {
 
     pub assert_untainted: bool,
 
+    /**
+     * The type the function guarantees this argument has once it returns,
+     * from a <<Hakana\AssertsType('...')>> attribute
+     */
+    pub asserted_type: Option<TUnion>,
+
     /**
      * Was the type inferred in a closure (e.g. one passed to Vec\Map)
      */
@@ -74,6 +80,7 @@ impl FunctionLikeParameter {
             is_variadic: false,
             taint_sinks: None,
             assert_untainted: false,
+            asserted_type: None,
             type_inferred: false,
             expect_variable: false,
             promoted_property: false,
@@ -15,10 +15,15 @@ use crate::{
 #[derive(Clone, PartialEq, Eq, Hash, Display, Debug, Serialize, Deserialize, EnumString)]
 pub enum IssueKind {
     AbstractInstantiation,
+    AbstractMethodCall,
     BannedFunction,
     ExtendFinalClass,
     CannotInferGenericParam,
     CustomIssue(Box<String>),
+    DeprecatedClass,
+    DeprecatedFunction,
+    DeprecatedMethod,
+    DuplicateCase,
     DuplicateEnumValue,
     EmptyBlock,
     FalsableReturnStatement,
@@ -33,12 +38,18 @@ pub enum IssueKind {
     ImpossibleNullTypeComparison,
     ImpossibleTruthinessCheck,
     ImpossibleTypeComparison,
+    IncompatibleClosureParameter,
     IncompatibleTypeParameters,
     InternalError,
     InvalidArgument,
     InvalidArrayOffset,
+    InvalidComparator,
+    InvalidConcatOperand,
     InvalidContainsCheck,
+    InvalidEchoOperand,
+    InvalidFormatArgument,
     InvalidHackFile,
+    InvalidInlineTypeAssertion,
     InvalidInoutArgument,
     InvalidMethodCall,
     InvalidPropertyAssignmentValue,
@@ -52,6 +63,7 @@ pub enum IssueKind {
     LessSpecificNestedReturnStatement,
     LessSpecificReturnStatement,
     MethodCallOnNull,
+    MissingOverrideAttribute,
     MissingRequiredXhpAttribute,
     MixedAnyArgument,
     MixedAnyArrayAccess,
@@ -104,6 +116,9 @@ pub enum IssueKind {
     PossiblyUndefinedIntArrayOffset,
     PossiblyUndefinedStringArrayOffset,
     PropertyTypeCoercion,
+    RedundantBooleanComparison,
+    RedundantCast,
+    RedundantDefault,
     RedundantIssetCheck,
     RedundantKeyCheck,
     RedundantNonnullEntryCheck,
@@ -114,6 +129,8 @@ pub enum IssueKind {
     StrictObjectEquality,
     TaintedData(Box<SinkType>),
     TestOnlyCall,
+    TooFewArguments,
+    TooManyArguments,
     UndefinedIntArrayOffset,
     UndefinedStringArrayOffset,
     UndefinedVariable,
@@ -123,6 +140,8 @@ pub enum IssueKind {
     UnrecognizedExpression,
     UnrecognizedStatement,
     UnrecognizedUnaryOp,
+    UnresolvedTemplate,
+    UnsafeInstantiation,
     UnusedAssignment,
     UnusedAssignmentInClosure,
     UnusedAssignmentStatement,
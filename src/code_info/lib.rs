@@ -4,6 +4,7 @@ pub mod assertion;
 pub mod ast;
 pub mod ast_signature;
 pub mod attribute_info;
+pub mod call_graph;
 pub mod class_constant_info;
 pub mod class_type_alias;
 pub mod classlike_info;
@@ -21,6 +22,7 @@ pub mod issue;
 pub mod member_visibility;
 pub mod method_identifier;
 pub mod method_info;
+pub mod mixed_source_profile;
 pub mod property_info;
 pub mod symbol_references;
 pub mod t_atomic;
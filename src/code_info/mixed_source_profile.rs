@@ -0,0 +1,111 @@
+use hakana_str::{Interner, StrId};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    analysis_result::AnalysisResult, codebase_info::CodebaseInfo, data_flow::node::DataFlowNodeId,
+    functionlike_identifier::FunctionLikeIdentifier,
+};
+
+/// A ranking of the functions, methods and properties that mixed values used in
+/// analyzed code most often originate from
+#[derive(Debug, Default)]
+pub struct MixedSourceProfile {
+    // Sources declared in analyzed code, with the number of places their mixed
+    // values end up, most frequent first
+    pub user_defined: Vec<(String, usize)>,
+
+    // Builtins and other stubbed sources, kept separate because many of them
+    // return mixed legitimately (e.g. json_decode)
+    pub builtins: Vec<(String, usize)>,
+}
+
+impl MixedSourceProfile {
+    pub fn new(
+        analysis_result: &AnalysisResult,
+        codebase: &CodebaseInfo,
+        interner: &Interner,
+    ) -> Self {
+        let mut positions_by_source: FxHashMap<(String, bool), FxHashSet<&String>> =
+            FxHashMap::default();
+
+        for (source_id, positions) in &analysis_result.mixed_source_counts {
+            let source = match source_id {
+                DataFlowNodeId::CallTo(functionlike_id)
+                | DataFlowNodeId::SpecializedCallTo(functionlike_id, ..) => (
+                    functionlike_id.to_string(interner),
+                    is_user_defined_functionlike(functionlike_id, codebase),
+                ),
+                DataFlowNodeId::Property(classlike_name, property_name)
+                | DataFlowNodeId::SpecializedProperty(classlike_name, property_name, ..) => (
+                    format!(
+                        "{}::${}",
+                        interner.lookup(classlike_name),
+                        interner.lookup(property_name)
+                    ),
+                    is_user_defined_classlike(classlike_name, codebase),
+                ),
+                _ => continue,
+            };
+
+            // specialized calls to the same function are counted together
+            positions_by_source
+                .entry(source)
+                .or_default()
+                .extend(positions);
+        }
+
+        let mut profile = MixedSourceProfile::default();
+
+        for ((name, user_defined), positions) in positions_by_source {
+            if user_defined {
+                profile.user_defined.push((name, positions.len()));
+            } else {
+                profile.builtins.push((name, positions.len()));
+            }
+        }
+
+        for sources in [&mut profile.user_defined, &mut profile.builtins] {
+            sources.sort_by(|(a_name, a_count), (b_name, b_count)| {
+                b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+            });
+        }
+
+        profile
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec!["Mixed sources in analyzed code:".to_string()];
+
+        for (name, count) in &self.user_defined {
+            lines.push(format!("{}\t{}", count, name));
+        }
+
+        lines.push("\nMixed sources in builtins:".to_string());
+
+        for (name, count) in &self.builtins {
+            lines.push(format!("{}\t{}", count, name));
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn is_user_defined_functionlike(
+    functionlike_id: &FunctionLikeIdentifier,
+    codebase: &CodebaseInfo,
+) -> bool {
+    match functionlike_id {
+        FunctionLikeIdentifier::Closure(..) => true,
+        _ => codebase
+            .functionlike_infos
+            .get(&functionlike_id.to_ref())
+            .map_or(false, |functionlike_info| functionlike_info.user_defined),
+    }
+}
+
+fn is_user_defined_classlike(classlike_name: &StrId, codebase: &CodebaseInfo) -> bool {
+    codebase
+        .classlike_infos
+        .get(classlike_name)
+        .map_or(false, |classlike_info| classlike_info.user_defined)
+}
@@ -447,6 +447,14 @@ pub(crate) fn scan(
                         .get_single_owned(),
                     ));
                 }
+
+                for included_enum in &enum_node.includes {
+                    if let oxidized::tast::Hint_::Happly(name, _) = &*included_enum.1 {
+                        storage
+                            .enum_includes
+                            .push(*resolved_names.get(&(name.0.start_offset() as u32)).unwrap());
+                    }
+                }
             }
 
             storage.template_extended_offsets.insert(
@@ -546,6 +554,15 @@ pub(crate) fn scan(
         }
     }
 
+    for included_enum in &storage.enum_includes {
+        let mut hasher = rustc_hash::FxHasher::default();
+        included_enum.0.hash(&mut hasher);
+
+        def_signature_node.signature_hash = def_signature_node
+            .signature_hash
+            .wrapping_add(hasher.finish());
+    }
+
     for class_const_node in &classlike_node.consts {
         visit_class_const_declaration(
             class_const_node,
@@ -595,6 +612,18 @@ pub(crate) fn scan(
             StrId::HAKANA_NOT_TEST_ONLY => {
                 storage.is_production_code = true;
             }
+            StrId::CONSISTENT_CONSTRUCT => {
+                storage.preserve_constructor_signature = true;
+            }
+            StrId::DEPRECATED => {
+                storage.is_deprecated = true;
+
+                if let Some(attribute_param_expr) = user_attribute.params.first() {
+                    if let aast::Expr_::String(str) = &attribute_param_expr.2 {
+                        storage.deprecation_message = Some(interner.intern(str.to_string()));
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -954,6 +983,7 @@ fn visit_class_const_declaration(
             None
         },
         unresolved_value: None,
+        aliased_constant: None,
         is_abstract: matches!(const_node.kind, ClassConstKind::CCAbstract(..)),
     };
 
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::simple_type_inferer;
 use crate::typehint_resolver::get_type_from_hint;
 use crate::typehint_resolver::get_type_from_optional_hint;
+use crate::typehint_resolver::get_type_from_string;
 use hakana_reflection_info::attribute_info::AttributeInfo;
 use hakana_reflection_info::classlike_info::ClassLikeInfo;
 use hakana_reflection_info::code_location::HPos;
@@ -55,6 +56,8 @@ pub(crate) fn scan_method(
         template_supers: vec![],
     };
 
+    let namespace = get_symbol_namespace(interner, classlike_name);
+
     let mut functionlike_info = get_functionlike(
         interner,
         all_custom_issues,
@@ -71,6 +74,7 @@ pub(crate) fn scan_method(
         &m.where_constraints,
         &mut type_resolution_context,
         Some(&classlike_name),
+        namespace.as_deref(),
         resolved_names,
         comments,
         file_source,
@@ -151,6 +155,7 @@ pub(crate) fn get_functionlike(
     where_constraints: &Vec<WhereConstraintHint>,
     type_context: &mut TypeResolutionContext,
     this_name: Option<&StrId>,
+    namespace: Option<&str>,
     resolved_names: &FxHashMap<u32, StrId>,
     comments: &Vec<(Pos, Comment)>,
     file_source: &FileSource,
@@ -303,6 +308,8 @@ pub(crate) fn get_functionlike(
             type_context,
             file_source,
             all_custom_issues,
+            interner,
+            namespace,
             comments
                 .iter()
                 .filter(|comment| comment.0.start_offset() > def_pos.start_offset())
@@ -381,6 +388,19 @@ pub(crate) fn get_functionlike(
             StrId::HAKANA_SECURITY_ANALYSIS_IGNORE_PATH_IF_TRUE => {
                 functionlike_info.ignore_taints_if_true = true;
             }
+            StrId::HAKANA_SECURITY_ANALYSIS_SAFE_RETURN => {
+                functionlike_info.safe_return = true;
+            }
+            StrId::DEPRECATED => {
+                functionlike_info.deprecated = true;
+
+                if let Some(attribute_param_expr) = user_attribute.params.first() {
+                    if let aast::Expr_::String(str) = &attribute_param_expr.2 {
+                        functionlike_info.deprecation_message =
+                            Some(interner.intern(str.to_string()));
+                    }
+                }
+            }
             StrId::HAKANA_SECURITY_ANALYSIS_SANITIZE | StrId::HAKANA_FIND_PATHS_SANITIZE => {
                 let mut removed_types = vec![];
 
@@ -407,6 +427,7 @@ pub(crate) fn get_functionlike(
             StrId::ENTRY_POINT => {
                 functionlike_info.dynamically_callable = true;
                 functionlike_info.ignore_taint_path = true;
+                functionlike_info.is_entry_point = true;
             }
             StrId::DYNAMICALLY_CALLABLE => {
                 functionlike_info.dynamically_callable = true;
@@ -566,6 +587,13 @@ fn is_async_call_is_same_as_sync(
     true
 }
 
+pub(crate) fn get_symbol_namespace(interner: &ThreadedInterner, symbol: StrId) -> Option<String> {
+    interner
+        .lookup(symbol)
+        .rsplit_once('\\')
+        .map(|(namespace, _)| namespace.to_string())
+}
+
 #[allow(clippy::ptr_arg)]
 pub(crate) fn adjust_location_from_comments(
     comments: &Vec<(Pos, Comment)>,
@@ -615,6 +643,8 @@ fn convert_param_nodes(
     type_context: &TypeResolutionContext,
     file_source: &FileSource,
     all_custom_issues: &FxHashSet<String>,
+    interner: &mut ThreadedInterner,
+    namespace: Option<&str>,
     mut comments: Vec<&(Pos, Comment)>,
 ) -> Vec<FunctionLikeParameter> {
     param_nodes
@@ -729,6 +759,24 @@ fn convert_param_nodes(
 
                         param.removed_taints_when_returning_true = Some(removed_taints);
                     }
+                    StrId::HAKANA_ASSERTS_TYPE => {
+                        if let Some(attribute_param_expr) = user_attribute.params.first() {
+                            if let aast::Expr_::String(str) = &attribute_param_expr.2 {
+                                param.asserted_type =
+                                    get_type_from_string(&str.to_string(), &mut |name| {
+                                        Some(interner.intern(
+                                            if let Some(name) = name.strip_prefix('\\') {
+                                                name.to_string()
+                                            } else if let Some(namespace) = namespace {
+                                                format!("{}\\{}", namespace, name)
+                                            } else {
+                                                name.to_string()
+                                            },
+                                        ))
+                                    });
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -1,6 +1,7 @@
 use std::hash::Hash;
 use std::sync::Arc;
 
+use crate::type_hint_hook::{apply_type_hint_hooks, TypeHintHook};
 use crate::typehint_resolver::get_type_from_hint;
 use hakana_aast_helper::Uses;
 use hakana_reflection_info::attribute_info::AttributeInfo;
@@ -29,6 +30,7 @@ use rustc_hash::{FxHashMap, FxHashSet};
 mod classlike_scanner;
 mod functionlike_scanner;
 pub mod simple_type_inferer;
+pub mod type_hint_hook;
 pub mod typehint_resolver;
 
 #[derive(Clone)]
@@ -50,6 +52,7 @@ struct Scanner<'a> {
     file_source: FileSource<'a>,
     resolved_names: &'a FxHashMap<u32, StrId>,
     all_custom_issues: &'a FxHashSet<String>,
+    type_hint_hooks: &'a [&'a dyn TypeHintHook],
     user_defined: bool,
     closure_refs: Vec<u32>,
     ast_nodes: Vec<DefSignatureNode>,
@@ -71,6 +74,22 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
                         Some((ns.0 .0.start_offset() - 10, ns.0 .0.end_offset()));
                 }
             }
+            // a define() inside a function body or a conditional block may never run,
+            // so only calls that are top-level statements are constant declarations
+            aast::Def::Stmt(stmt) => {
+                if let aast::Stmt_::Expr(expr) = &stmt.1 {
+                    if let aast::Expr_::Call(boxed) = &expr.2 {
+                        if let aast::Expr_::Id(boxed_id) = &boxed.func.2 {
+                            if let (Some(&StrId::DEFINE), 2) = (
+                                self.resolved_names.get(&(boxed_id.0.start_offset() as u32)),
+                                boxed.args.len(),
+                            ) {
+                                self.visit_define_call(&boxed.args[0].1, &boxed.args[1].1);
+                            }
+                        }
+                    }
+                }
+            }
             aast::Def::NamespaceUse(uses) => {
                 for (_, name, alias_name) in uses {
                     let adjusted_start = name.0.to_raw_span().start.beg_of_line() as usize;
@@ -118,6 +137,26 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
             &self.uses,
         );
 
+        if !self.type_hint_hooks.is_empty() {
+            if let Some(classlike_storage) = self.codebase.classlike_infos.get_mut(&class_name) {
+                for property_storage in classlike_storage.properties.values_mut() {
+                    if property_storage.type_pos.is_some() {
+                        apply_type_hint_hooks(
+                            self.type_hint_hooks,
+                            self.interner,
+                            &mut property_storage.type_,
+                        );
+                    }
+                }
+
+                for constant_storage in classlike_storage.constants.values_mut() {
+                    if let Some(provided_type) = &mut constant_storage.provided_type {
+                        apply_type_hint_hooks(self.type_hint_hooks, self.interner, provided_type);
+                    }
+                }
+            }
+        }
+
         class.recurse(
             &mut Context {
                 classlike_name: Some(class_name),
@@ -182,6 +221,13 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
                 },
                 inferred_type: simple_type_inferer::infer(&gc.value, self.resolved_names),
                 unresolved_value: None,
+                aliased_constant: if let aast::Expr_::Id(id) = &gc.value.2 {
+                    self.resolved_names
+                        .get(&(id.0.start_offset() as u32))
+                        .copied()
+                } else {
+                    None
+                },
                 is_abstract: false,
             },
         );
@@ -504,6 +550,12 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
             c.has_static_field_access = false;
         }
 
+        apply_functionlike_type_hint_hooks(
+            self.type_hint_hooks,
+            self.interner,
+            functionlike_storage,
+        );
+
         result
     }
 
@@ -629,6 +681,67 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
 }
 
 impl<'a> Scanner<'a> {
+    fn visit_define_call(
+        &mut self,
+        name_expr: &aast::Expr<(), ()>,
+        value_expr: &aast::Expr<(), ()>,
+    ) {
+        let name = if let aast::Expr_::String(name) = &name_expr.2 {
+            self.interner
+                .intern(name.to_string().trim_start_matches('\\').to_string())
+        } else {
+            return;
+        };
+
+        // defining a constant twice fails at runtime, so the first definition wins
+        if self.codebase.constant_infos.contains_key(&name) {
+            return;
+        }
+
+        self.codebase
+            .const_files
+            .entry((self.file_source.file_path_actual).clone())
+            .or_default()
+            .insert(name);
+
+        let definition_location = HPos::new(&name_expr.1, self.file_source.file_path);
+
+        self.ast_nodes.push(DefSignatureNode {
+            name,
+            start_offset: definition_location.start_offset,
+            end_offset: definition_location.end_offset,
+            start_line: definition_location.start_line,
+            end_line: definition_location.end_line,
+            start_colum: definition_location.start_column,
+            end_column: definition_location.end_column,
+            children: Vec::new(),
+            signature_hash: position_insensitive_hash(value_expr),
+            body_hash: None,
+            is_function: false,
+            is_constant: true,
+        });
+
+        // a non-literal value leaves the constant untyped
+        self.codebase.constant_infos.insert(
+            name,
+            ConstantInfo {
+                pos: definition_location,
+                type_pos: None,
+                provided_type: None,
+                inferred_type: simple_type_inferer::infer(value_expr, self.resolved_names),
+                unresolved_value: None,
+                aliased_constant: if let aast::Expr_::Id(id) = &value_expr.2 {
+                    self.resolved_names
+                        .get(&(id.0.start_offset() as u32))
+                        .copied()
+                } else {
+                    None
+                },
+                is_abstract: false,
+            },
+        );
+    }
+
     fn visit_function(
         &mut self,
         c: &mut Context,
@@ -678,6 +791,12 @@ impl<'a> Scanner<'a> {
             template_supers: vec![],
         };
 
+        // closures resolve names in the namespace of the symbol they're declared in
+        let namespace = name
+            .or(c.classlike_name)
+            .or(c.function_name)
+            .and_then(|symbol| functionlike_scanner::get_symbol_namespace(self.interner, symbol));
+
         let mut functionlike_storage = functionlike_scanner::get_functionlike(
             self.interner,
             self.all_custom_issues,
@@ -694,6 +813,7 @@ impl<'a> Scanner<'a> {
             where_constraints,
             &mut type_resolution_context,
             None,
+            namespace.as_deref(),
             self.resolved_names,
             self.file_source.comments,
             &self.file_source,
@@ -727,6 +847,12 @@ impl<'a> Scanner<'a> {
             }
         }
 
+        apply_functionlike_type_hint_hooks(
+            self.type_hint_hooks,
+            self.interner,
+            &mut functionlike_storage,
+        );
+
         if c.has_yield {
             functionlike_storage.has_yield = true;
             c.has_yield = false;
@@ -746,6 +872,26 @@ impl<'a> Scanner<'a> {
     }
 }
 
+fn apply_functionlike_type_hint_hooks(
+    type_hint_hooks: &[&dyn TypeHintHook],
+    interner: &mut ThreadedInterner,
+    functionlike_storage: &mut FunctionLikeInfo,
+) {
+    if type_hint_hooks.is_empty() {
+        return;
+    }
+
+    for param in functionlike_storage.params.iter_mut() {
+        if let Some(signature_type) = &mut param.signature_type {
+            apply_type_hint_hooks(type_hint_hooks, interner, signature_type);
+        }
+    }
+
+    if let Some(return_type) = &mut functionlike_storage.return_type {
+        apply_type_hint_hooks(type_hint_hooks, interner, return_type);
+    }
+}
+
 fn fix_function_return_type(function_name: StrId, functionlike_storage: &mut FunctionLikeInfo) {
     match function_name {
         // bool
@@ -872,6 +1018,7 @@ pub fn collect_info_for_aast(
     interner: &mut ThreadedInterner,
     codebase: &mut CodebaseInfo,
     all_custom_issues: &FxHashSet<String>,
+    type_hint_hooks: &[&dyn TypeHintHook],
     file_source: FileSource,
     user_defined: bool,
     uses: Uses,
@@ -885,6 +1032,7 @@ pub fn collect_info_for_aast(
         resolved_names,
         user_defined,
         all_custom_issues,
+        type_hint_hooks,
         closure_refs: vec![],
         ast_nodes: Vec::new(),
         uses,
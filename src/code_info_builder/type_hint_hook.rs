@@ -0,0 +1,32 @@
+use hakana_reflection_info::t_union::TUnion;
+use hakana_str::ThreadedInterner;
+
+pub trait TypeHintHook: Send + Sync + core::fmt::Debug {
+    // This hook is run during scanning on every type resolved from a parameter,
+    // return, property or class constant hint, before it's stored in the codebase.
+    // Named types are still unresolved at this point, so they appear as TReference.
+    // Returning None leaves the type unchanged.
+    fn transform_type_hint(
+        &self,
+        hint_type: &TUnion,
+        interner: &mut ThreadedInterner,
+    ) -> Option<TUnion>;
+}
+
+pub(crate) fn apply_type_hint_hooks(
+    type_hint_hooks: &[&dyn TypeHintHook],
+    interner: &mut ThreadedInterner,
+    hint_type: &mut TUnion,
+) {
+    // template params are bound per-signature, so types containing them (even
+    // nested, as in vec<T>) are never remapped
+    if hint_type.has_template_types() {
+        return;
+    }
+
+    for type_hint_hook in type_hint_hooks {
+        if let Some(new_type) = type_hint_hook.transform_type_hint(hint_type, interner) {
+            *hint_type = new_type;
+        }
+    }
+}
@@ -9,8 +9,18 @@ use hakana_reflection_info::EFFECT_IMPURE;
 use hakana_reflection_info::EFFECT_PURE;
 use hakana_str::StrId;
 use hakana_type::get_arraykey;
+use hakana_type::get_bool;
+use hakana_type::get_dict;
+use hakana_type::get_float;
+use hakana_type::get_int;
+use hakana_type::get_keyset;
+use hakana_type::get_mixed;
 use hakana_type::get_mixed_any;
 use hakana_type::get_nothing;
+use hakana_type::get_null;
+use hakana_type::get_num;
+use hakana_type::get_string;
+use hakana_type::get_vec;
 use hakana_type::wrap_atomic;
 use oxidized::aast::Hint;
 use oxidized::aast::Hint_;
@@ -21,6 +31,8 @@ use oxidized::ast_defs::ParamKind;
 use oxidized::tast::HintFun;
 use rustc_hash::FxHashMap;
 use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::str::Chars;
 use std::sync::Arc;
 
 fn get_vec_type_from_hint(
@@ -917,3 +929,112 @@ pub fn get_type_references_from_hint(
 
     refs
 }
+
+/// Parses a type written as a string, e.g. in a HAKANA_ASSERT comment or an
+/// attribute argument. Class, enum and alias names are passed to `resolve_name`.
+pub fn get_type_from_string(
+    type_string: &str,
+    resolve_name: &mut dyn FnMut(&str) -> Option<StrId>,
+) -> Option<TUnion> {
+    let mut chars = type_string.chars().peekable();
+
+    let parsed_type = parse_type(&mut chars, resolve_name)?;
+
+    if chars.next().is_some() {
+        return None;
+    }
+
+    Some(parsed_type)
+}
+
+// Handles the subset of Hack type syntax that's useful in an assertion: primitives,
+// nullable types, vec/dict/keyset and (possibly generic) class, enum and alias names
+fn parse_type(
+    chars: &mut Peekable<Chars>,
+    resolve_name: &mut dyn FnMut(&str) -> Option<StrId>,
+) -> Option<TUnion> {
+    skip_whitespace(chars);
+
+    if chars.peek() == Some(&'?') {
+        chars.next();
+
+        let mut inner_type = parse_type(chars, resolve_name)?;
+
+        if !inner_type.is_nullable() && !inner_type.is_mixed() {
+            inner_type.types.push(TAtomic::TNull);
+        }
+
+        return Some(inner_type);
+    }
+
+    let mut name = String::new();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '\\' {
+            name.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+
+    if name.is_empty() {
+        return None;
+    }
+
+    skip_whitespace(chars);
+
+    let mut type_params = vec![];
+
+    if chars.peek() == Some(&'<') {
+        chars.next();
+
+        loop {
+            type_params.push(parse_type(chars, resolve_name)?);
+
+            skip_whitespace(chars);
+
+            match chars.next()? {
+                ',' => {}
+                '>' => break,
+                _ => return None,
+            }
+        }
+
+        skip_whitespace(chars);
+    }
+
+    Some(match (name.as_str(), type_params.len()) {
+        ("int", 0) => get_int(),
+        ("string", 0) => get_string(),
+        ("float", 0) => get_float(),
+        ("bool", 0) => get_bool(),
+        ("num", 0) => get_num(),
+        ("arraykey", 0) => get_arraykey(false),
+        ("mixed", 0) => get_mixed(),
+        ("nonnull", 0) => wrap_atomic(TAtomic::TMixedWithFlags(false, false, false, true)),
+        ("null", 0) => get_null(),
+        ("nothing", 0) => get_nothing(),
+        ("vec", 1) => get_vec(type_params.pop().unwrap()),
+        ("keyset", 1) => get_keyset(type_params.pop().unwrap()),
+        ("dict", 2) => {
+            let value_param = type_params.pop().unwrap();
+            let key_param = type_params.pop().unwrap();
+            get_dict(key_param, value_param)
+        }
+        _ => wrap_atomic(TAtomic::TReference {
+            name: resolve_name(&name)?,
+            type_params: if type_params.is_empty() {
+                None
+            } else {
+                Some(type_params)
+            },
+        }),
+    })
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+        chars.next();
+    }
+}
@@ -39,7 +39,9 @@ pub fn analyze_files(
         paths.retain(|str_path| str_path.matches(filter.as_str()).count() > 0);
     }
 
-    paths.retain(|str_path| config.allow_issues_in_file(str_path));
+    paths.retain(|str_path| {
+        config.allow_issues_in_file(str_path) && config.allow_analysis_in_file(str_path)
+    });
 
     if let Some(ignored_paths) = &ignored_paths {
         for ignored_path in ignored_paths {
@@ -117,6 +119,16 @@ pub fn analyze_files(
                         resolved_names,
                         &logger,
                     );
+
+                    if let Some(issue_callback) = &analysis_config.issue_callback {
+                        if let Some(file_issues) =
+                            new_analysis_result.emitted_issues.get(&file_path)
+                        {
+                            for issue in file_issues {
+                                issue_callback.call(issue);
+                            }
+                        }
+                    }
                 }
 
                 let mut tally = files_processed.lock().unwrap();
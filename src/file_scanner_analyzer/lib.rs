@@ -186,7 +186,7 @@ pub async fn scan_and_analyze_async(
 
     let mut scan_data = Arc::try_unwrap(arc_scan_data).unwrap();
 
-    add_invalid_files(&scan_data, &mut analysis_result);
+    add_invalid_files(&scan_data, &config, &mut analysis_result);
 
     if config.find_unused_definitions {
         find_unused_definitions(
@@ -350,7 +350,7 @@ pub fn scan_and_analyze<F: FnOnce() -> ()>(
 
     let mut scan_data = Arc::try_unwrap(arc_scan_data).unwrap();
 
-    add_invalid_files(&scan_data, &mut analysis_result);
+    add_invalid_files(&scan_data, &config, &mut analysis_result);
 
     if config.find_unused_definitions {
         find_unused_definitions(
@@ -361,6 +361,12 @@ pub fn scan_and_analyze<F: FnOnce() -> ()>(
             &ignored_paths,
             &mut scan_data.file_system,
         );
+
+        if let Some(issue_callback) = &config.issue_callback {
+            for issue in analysis_result.emitted_definition_issues.values().flatten() {
+                issue_callback.call(issue);
+            }
+        }
     }
 
     if let GraphKind::WholeProgram(whole_program_kind) = config.graph_kind {
@@ -380,6 +386,10 @@ pub fn scan_and_analyze<F: FnOnce() -> ()>(
         };
 
         for issue in issues {
+            if let Some(issue_callback) = &config.issue_callback {
+                issue_callback.call(&issue);
+            }
+
             analysis_result
                 .emitted_issues
                 .entry(issue.pos.file_path)
@@ -402,6 +412,14 @@ fn get_analysis_ready(
 ) -> (Arc<Mutex<AnalysisResult>>, Arc<SuccessfulScanData>) {
     let mut analysis_result = AnalysisResult::new(config.graph_kind, symbol_references);
 
+    // files whose issues come from the cache aren't analyzed again, so the callback
+    // would otherwise never see them
+    if let Some(issue_callback) = &config.issue_callback {
+        for issue in existing_issues.values().flatten() {
+            issue_callback.call(issue);
+        }
+    }
+
     analysis_result.emitted_issues = existing_issues;
 
     let analysis_result = Arc::new(Mutex::new(analysis_result));
@@ -479,45 +497,54 @@ fn update_progressbar(percentage: u64, bar: Option<Arc<ProgressBar>>) {
     }
 }
 
-fn add_invalid_files(scan_data: &SuccessfulScanData, analysis_result: &mut AnalysisResult) {
+fn add_invalid_files(
+    scan_data: &SuccessfulScanData,
+    config: &Config,
+    analysis_result: &mut AnalysisResult,
+) {
     for (file_path, file_info) in &scan_data.codebase.files {
         if let Some(parser_error) = &file_info.parser_error {
-            analysis_result.emitted_issues.insert(
-                *file_path,
-                vec![match parser_error {
-                    ParserError::NotAHackFile => Issue::new(
-                        IssueKind::InvalidHackFile,
-                        "Invalid Hack file".to_string(),
-                        HPos {
-                            file_path: *file_path,
-                            start_offset: 0,
-                            end_offset: 0,
-                            start_line: 0,
-                            end_line: 0,
-                            start_column: 0,
-                            end_column: 0,
-                        },
-                        &None,
-                    ),
-                    ParserError::CannotReadFile => Issue::new(
-                        IssueKind::InvalidHackFile,
-                        "Cannot read file".to_string(),
-                        HPos {
-                            file_path: *file_path,
-                            start_offset: 0,
-                            end_offset: 0,
-                            start_line: 0,
-                            end_line: 0,
-                            start_column: 0,
-                            end_column: 0,
-                        },
-                        &None,
-                    ),
-                    ParserError::SyntaxError { message, pos } => {
-                        Issue::new(IssueKind::InvalidHackFile, message.clone(), *pos, &None)
-                    }
-                }],
-            );
+            let issue = match parser_error {
+                ParserError::NotAHackFile => Issue::new(
+                    IssueKind::InvalidHackFile,
+                    "Invalid Hack file".to_string(),
+                    HPos {
+                        file_path: *file_path,
+                        start_offset: 0,
+                        end_offset: 0,
+                        start_line: 0,
+                        end_line: 0,
+                        start_column: 0,
+                        end_column: 0,
+                    },
+                    &None,
+                ),
+                ParserError::CannotReadFile => Issue::new(
+                    IssueKind::InvalidHackFile,
+                    "Cannot read file".to_string(),
+                    HPos {
+                        file_path: *file_path,
+                        start_offset: 0,
+                        end_offset: 0,
+                        start_line: 0,
+                        end_line: 0,
+                        start_column: 0,
+                        end_column: 0,
+                    },
+                    &None,
+                ),
+                ParserError::SyntaxError { message, pos } => {
+                    Issue::new(IssueKind::InvalidHackFile, message.clone(), *pos, &None)
+                }
+            };
+
+            if let Some(issue_callback) = &config.issue_callback {
+                issue_callback.call(&issue);
+            }
+
+            analysis_result
+                .emitted_issues
+                .insert(*file_path, vec![issue]);
         }
     }
 }
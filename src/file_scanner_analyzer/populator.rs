@@ -303,6 +303,16 @@ fn populate_functionlike_storage(
             );
         }
 
+        if let Some(ref mut asserted_type) = param.asserted_type {
+            populate_union_type(
+                asserted_type,
+                codebase_symbols,
+                reference_source,
+                symbol_references,
+                force_type_population,
+            );
+        }
+
         for attribute_info in &param.attributes {
             match reference_source {
                 ReferenceSource::Symbol(in_signature, a) => symbol_references
@@ -429,6 +439,19 @@ fn populate_classlike_storage(
         );
     }
 
+    let mut visited_enums = FxHashSet::default();
+    visited_enums.insert(*classlike_name);
+
+    for included_enum in &storage.enum_includes.clone() {
+        populate_data_from_included_enum(
+            &mut storage,
+            codebase,
+            included_enum,
+            &mut visited_enums,
+            symbol_references,
+        );
+    }
+
     for direct_parent_interface in &storage.direct_parent_interfaces.clone() {
         populate_interface_data_from_parent_interface(
             &mut storage,
@@ -511,6 +534,53 @@ fn populate_interface_data_from_parent_or_implemented_interface(
     // todo update dependent classlikes
 }
 
+fn populate_data_from_included_enum(
+    storage: &mut ClassLikeInfo,
+    codebase: &CodebaseInfo,
+    included_enum: &StrId,
+    visited_enums: &mut FxHashSet<StrId>,
+    symbol_references: &mut SymbolReferences,
+) {
+    // enums can include each other in a cycle, so each one is only merged once
+    if !visited_enums.insert(*included_enum) {
+        return;
+    }
+
+    symbol_references.add_symbol_reference_to_symbol(storage.name, *included_enum, true);
+
+    let included_enum_storage =
+        if let Some(included_enum_storage) = codebase.classlike_infos.get(included_enum) {
+            included_enum_storage
+        } else {
+            storage.invalid_dependencies.push(*included_enum);
+            return;
+        };
+
+    // on a name collision the including enum's own case wins
+    storage.constants.extend(
+        included_enum_storage
+            .constants
+            .iter()
+            .filter(|(k, _)| !storage.constants.contains_key(*k))
+            .map(|v| (*v.0, v.1.clone()))
+            .collect::<FxHashMap<_, _>>(),
+    );
+
+    storage
+        .invalid_dependencies
+        .extend(included_enum_storage.invalid_dependencies.clone());
+
+    for nested_enum in &included_enum_storage.enum_includes {
+        populate_data_from_included_enum(
+            storage,
+            codebase,
+            nested_enum,
+            visited_enums,
+            symbol_references,
+        );
+    }
+}
+
 fn populate_interface_data_from_parent_interface(
     storage: &mut ClassLikeInfo,
     codebase: &mut CodebaseInfo,
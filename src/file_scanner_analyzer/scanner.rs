@@ -29,6 +29,7 @@ use hakana_reflection_info::diff::CodebaseDiff;
 use hakana_reflection_info::file_info::FileInfo;
 use hakana_reflection_info::file_info::ParserError;
 use hakana_reflection_info::FileSource;
+use hakana_reflector::type_hint_hook::TypeHintHook;
 use hakana_str::Interner;
 use hakana_str::StrId;
 use hakana_str::ThreadedInterner;
@@ -316,6 +317,11 @@ pub fn scan_files(
                 let mut new_interner = ThreadedInterner::new(interner);
                 let empty_name_context = NameContext::new(&mut new_interner);
                 let mut local_resolved_names = FxHashMap::default();
+                let type_hint_hooks = config
+                    .hooks
+                    .iter()
+                    .filter_map(|hook| hook.get_type_hint_hook())
+                    .collect::<Vec<_>>();
 
                 for file_path in &pgc {
                     let str_path = new_interner
@@ -329,6 +335,7 @@ pub fn scan_files(
                         &str_path,
                         *file_path,
                         &config.all_custom_issues,
+                        &type_hint_hooks,
                         &mut new_codebase,
                         &mut new_interner,
                         empty_name_context.clone(),
@@ -504,6 +511,7 @@ pub(crate) fn scan_file(
     str_path: &str,
     file_path: FilePath,
     all_custom_issues: &FxHashSet<String>,
+    type_hint_hooks: &[&dyn TypeHintHook],
     codebase: &mut CodebaseInfo,
     interner: &mut ThreadedInterner,
     empty_name_context: NameContext<'_>,
@@ -531,6 +539,7 @@ pub(crate) fn scan_file(
         interner,
         codebase,
         all_custom_issues,
+        type_hint_hooks,
         FileSource {
             is_production_code,
             file_path_actual: str_path.to_string(),
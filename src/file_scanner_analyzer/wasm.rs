@@ -117,6 +117,7 @@ pub fn get_single_file_codebase(
             file.as_ref(),
             interned_file_path,
             &FxHashSet::default(),
+            &[],
             &mut codebase,
             &mut threaded_interner,
             empty_name_context.clone(),
@@ -138,6 +139,7 @@ pub fn get_single_file_codebase(
             file.as_ref(),
             interned_file_path,
             &FxHashSet::default(),
+            &[],
             &mut codebase,
             &mut threaded_interner,
             empty_name_context.clone(),
@@ -158,6 +160,7 @@ pub fn get_single_file_codebase(
             str_path,
             interned_file_path,
             &FxHashSet::default(),
+            &[],
             &mut codebase,
             &mut threaded_interner,
             empty_name_context.clone(),
@@ -209,6 +212,7 @@ pub fn scan_single_file(
         interner,
         codebase,
         &FxHashSet::default(),
+        &[],
         FileSource {
             is_production_code: true,
             file_path_actual: path.clone(),
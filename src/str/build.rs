@@ -199,6 +199,7 @@ fn main() -> Result<()> {
         "HH\\Lib\\Vec\\reverse",
         "HH\\Lib\\Vec\\slice",
         "HH\\Lib\\Vec\\sort",
+        "HH\\Lib\\Vec\\sort_by",
         "HH\\Lib\\Vec\\take",
         "HH\\Lib\\Vec\\unique",
         "HH\\Lib\\Vec\\zip",
@@ -238,6 +239,7 @@ fn main() -> Result<()> {
         "HH\\type_structure_for_alias",
         "HH\\varray",
         "HH\\vec",
+        "Hakana\\AssertsType",
         "Hakana\\BannedFunction",
         "Hakana\\FindPaths\\Sanitize",
         "Hakana\\NotTestOnly",
@@ -246,6 +248,7 @@ fn main() -> Result<()> {
         "Hakana\\SecurityAnalysis\\IgnorePath",
         "Hakana\\SecurityAnalysis\\IgnorePathIfTrue",
         "Hakana\\SecurityAnalysis\\RemoveTaintsWhenReturningTrue",
+        "Hakana\\SecurityAnalysis\\SafeReturn",
         "Hakana\\SecurityAnalysis\\Sanitize",
         "Hakana\\SecurityAnalysis\\ShapeSource",
         "Hakana\\SecurityAnalysis\\Sink",
@@ -260,7 +263,9 @@ fn main() -> Result<()> {
         "ReflectionTypeAlias",
         "SimpleXMLElement",
         "XHPChild",
+        "__ConsistentConstruct",
         "__DIR__",
+        "__Deprecated",
         "__DynamicallyCallable",
         "__EntryPoint",
         "__FILE__",
@@ -269,19 +274,31 @@ fn main() -> Result<()> {
         "__PHP_Incomplete_Class",
         "__Sealed",
         "__construct",
+        "__toString",
         "abs",
         "addcslashes",
         "addslashes",
+        "array_chunk",
         "array_combine",
+        "array_count_values",
+        "array_filter",
+        "array_flip",
+        "array_intersect",
+        "array_is_list",
         "array_key_exists",
         "array_keys",
+        "array_map",
         "array_merge",
+        "array_product",
         "array_push",
         "array_reverse",
         "array_shift",
         "array_slice",
+        "array_splice",
+        "array_sum",
         "array_unique",
         "array_unshift",
+        "array_walk",
         "arsort",
         "asin",
         "asort",
@@ -293,6 +310,7 @@ fn main() -> Result<()> {
         "base64_encode",
         "basename",
         "bin2hex",
+        "bindec",
         "ceil",
         "chop",
         "chr",
@@ -317,6 +335,8 @@ fn main() -> Result<()> {
         "debug_backtrace",
         "decbin",
         "dechex",
+        "decoct",
+        "define",
         "deg2rad",
         "dirname",
         "echo",
@@ -413,6 +433,7 @@ fn main() -> Result<()> {
         "mysql_escape_string",
         "nl2br",
         "number_format",
+        "octdec",
         "ord",
         "pack",
         "parent",
@@ -449,6 +470,7 @@ fn main() -> Result<()> {
         "rtrim",
         "self",
         "serialize",
+        "settype",
         "sha1",
         "socket_strerror",
         "sort",
@@ -456,6 +478,8 @@ fn main() -> Result<()> {
         "sscanf",
         "static",
         "stdClass",
+        "str_contains",
+        "str_ends_with",
         "str_ireplace",
         "str_pad",
         "str_repeat",
@@ -463,6 +487,7 @@ fn main() -> Result<()> {
         "str_rot13",
         "str_shuffle",
         "str_split",
+        "str_starts_with",
         "str_word_count",
         "strcasecmp",
         "strchr",
@@ -501,17 +526,21 @@ fn main() -> Result<()> {
         "toDict",
         "trigger_error",
         "trim",
+        "uasort",
         "ucfirst",
         "ucwords",
+        "uksort",
         "unpack",
         "unset",
         "urldecode",
         "urlencode",
+        "usort",
         "utf8_decode",
         "utf8_encode",
         "var_dump",
         "var_export",
         "version_compare",
+        "vprintf",
         "vsprintf",
         "wordwrap",
     ];
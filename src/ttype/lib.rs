@@ -466,6 +466,59 @@ pub fn get_value_param(atomic: &TAtomic, codebase: &CodebaseInfo) -> Option<TUni
     }
 }
 
+const GETTYPE_NAMES: [&str; 10] = [
+    "boolean", "integer", "double", "string", "vec", "dict", "keyset", "object", "resource", "NULL",
+];
+
+/// Returns the possible results of calling `gettype()` on a value of the given type
+pub fn get_gettype_names(atomic: &TAtomic) -> Vec<&'static str> {
+    match atomic {
+        TAtomic::TInt | TAtomic::TLiteralInt { .. } => vec!["integer"],
+        TAtomic::TFloat => vec!["double"],
+        TAtomic::TNum => vec!["integer", "double"],
+        TAtomic::TArraykey { .. } => vec!["integer", "string"],
+        TAtomic::TScalar => vec!["boolean", "integer", "double", "string"],
+        TAtomic::TBool | TAtomic::TTrue | TAtomic::TFalse => vec!["boolean"],
+        TAtomic::TNull => vec!["NULL"],
+        TAtomic::TVec { .. } => vec!["vec"],
+        TAtomic::TDict { .. } => vec!["dict"],
+        TAtomic::TKeyset { .. } => vec!["keyset"],
+        TAtomic::TNamedObject { .. }
+        | TAtomic::TObject
+        | TAtomic::TAwaitable { .. }
+        | TAtomic::TClosure { .. }
+        | TAtomic::TClosureAlias { .. } => vec!["object"],
+        TAtomic::TResource => vec!["resource"],
+        TAtomic::TGenericParam { as_type, .. } => {
+            as_type.types.iter().flat_map(get_gettype_names).collect()
+        }
+        _ => {
+            if atomic.is_string() {
+                vec!["string"]
+            } else {
+                GETTYPE_NAMES.to_vec()
+            }
+        }
+    }
+}
+
+/// Returns the types a value can have when `gettype()` returns the given name
+pub fn get_types_for_gettype_name(name: &str) -> Option<Vec<TAtomic>> {
+    Some(match name {
+        "boolean" => vec![TAtomic::TBool],
+        "integer" => vec![TAtomic::TInt],
+        "double" => vec![TAtomic::TFloat],
+        "string" => vec![TAtomic::TString],
+        "vec" => get_mixed_vec().types,
+        "dict" => get_mixed_dict().types,
+        "keyset" => get_mixed_keyset().types,
+        "object" => vec![TAtomic::TObject],
+        "resource" => vec![TAtomic::TResource],
+        "NULL" => vec![TAtomic::TNull],
+        _ => return None,
+    })
+}
+
 pub fn get_union_syntax_type(
     union: &TUnion,
     codebase: &CodebaseInfo,
@@ -32,6 +32,7 @@ pub fn replace(
     add_lower_bound: bool,                   // false
     bound_equality_classlike: Option<StrId>, // None
     depth: usize,                            // 1
+    widen_loop_isset_mixed: bool,            // false
 ) -> TUnion {
     let mut atomic_types = Vec::new();
 
@@ -72,6 +73,7 @@ pub fn replace(
             depth,
             original_atomic_types.len() == 1,
             &mut had_template,
+            widen_loop_isset_mixed,
         ))
     }
 
@@ -113,6 +115,7 @@ fn handle_atomic_standin(
     depth: usize,
     was_single: bool,
     had_template: &mut bool,
+    widen_loop_isset_mixed: bool,
 ) -> Vec<TAtomic> {
     let normalized_key = if let TAtomic::TNamedObject { name, .. } = atomic_type {
         name.0.to_string()
@@ -149,6 +152,7 @@ fn handle_atomic_standin(
                 bound_equality_classlike,
                 depth,
                 had_template,
+                widen_loop_isset_mixed,
             );
         }
     }
@@ -181,6 +185,7 @@ fn handle_atomic_standin(
                 bound_equality_classlike,
                 depth,
                 was_single,
+                widen_loop_isset_mixed,
             );
         }
     }
@@ -213,6 +218,7 @@ fn handle_atomic_standin(
                 bound_equality_classlike,
                 depth,
                 was_single,
+                widen_loop_isset_mixed,
             );
         }
     }
@@ -245,6 +251,7 @@ fn handle_atomic_standin(
             replace,
             add_lower_bound,
             depth + 1,
+            widen_loop_isset_mixed,
         );
 
         return vec![atomic_type];
@@ -265,6 +272,7 @@ fn handle_atomic_standin(
             replace,
             add_lower_bound,
             depth + 1,
+            widen_loop_isset_mixed,
         ))
     }
 
@@ -283,6 +291,7 @@ fn replace_atomic(
     replace: bool,
     add_lower_bound: bool,
     depth: usize,
+    widen_loop_isset_mixed: bool,
 ) -> TAtomic {
     let mut atomic_type = atomic_type.clone();
 
@@ -321,6 +330,7 @@ fn replace_atomic(
                         add_lower_bound,
                         None,
                         depth,
+                        widen_loop_isset_mixed,
                     ));
                 }
             } else if let Some(params) = params {
@@ -351,6 +361,7 @@ fn replace_atomic(
                     add_lower_bound,
                     None,
                     depth,
+                    widen_loop_isset_mixed,
                 ));
 
                 params.1 = Box::new(self::replace(
@@ -370,6 +381,7 @@ fn replace_atomic(
                     add_lower_bound,
                     None,
                     depth,
+                    widen_loop_isset_mixed,
                 ));
             }
 
@@ -409,6 +421,7 @@ fn replace_atomic(
                         add_lower_bound,
                         None,
                         depth,
+                        widen_loop_isset_mixed,
                     );
                 }
             } else {
@@ -435,6 +448,7 @@ fn replace_atomic(
                     add_lower_bound,
                     None,
                     depth,
+                    widen_loop_isset_mixed,
                 ));
             }
 
@@ -463,6 +477,7 @@ fn replace_atomic(
                 add_lower_bound,
                 None,
                 depth,
+                widen_loop_isset_mixed,
             ));
 
             return atomic_type;
@@ -488,6 +503,7 @@ fn replace_atomic(
                 add_lower_bound,
                 None,
                 depth,
+                widen_loop_isset_mixed,
             ));
 
             return atomic_type;
@@ -542,7 +558,9 @@ fn replace_atomic(
                                     _ => None,
                                 }
                             }
-                            TAtomic::TMixedFromLoopIsset => Some(get_mixed_maybe_from_loop(true)),
+                            TAtomic::TMixedFromLoopIsset => {
+                                Some(get_mixed_maybe_from_loop(!widen_loop_isset_mixed))
+                            }
                             TAtomic::TMixed | TAtomic::TMixedWithFlags(..) => Some(get_mixed_any()),
                             _ => None,
                         },
@@ -570,6 +588,7 @@ fn replace_atomic(
                         add_lower_bound,
                         None,
                         depth,
+                        widen_loop_isset_mixed,
                     );
                 }
             }
@@ -615,6 +634,7 @@ fn replace_atomic(
                         add_lower_bound,
                         None,
                         depth,
+                        widen_loop_isset_mixed,
                     );
                 }
             }
@@ -659,6 +679,7 @@ fn replace_atomic(
                         !add_lower_bound,
                         None,
                         depth,
+                        widen_loop_isset_mixed,
                     ));
                 }
             }
@@ -685,6 +706,7 @@ fn replace_atomic(
                     add_lower_bound,
                     None,
                     depth - 1,
+                    widen_loop_isset_mixed,
                 ));
             }
 
@@ -710,6 +732,7 @@ fn replace_atomic(
                 replace,
                 add_lower_bound,
                 depth,
+                widen_loop_isset_mixed,
             ));
 
             return atomic_type;
@@ -734,6 +757,7 @@ fn replace_atomic(
                 replace,
                 add_lower_bound,
                 depth,
+                widen_loop_isset_mixed,
             ));
 
             return atomic_type;
@@ -760,6 +784,7 @@ fn handle_template_param_standin(
     bound_equality_classlike: Option<StrId>,
     depth: usize,
     had_template: &mut bool,
+    widen_loop_isset_mixed: bool,
 ) -> Vec<TAtomic> {
     let (param_name, defining_entity, extra_types, as_type) = if let TAtomic::TGenericParam {
         param_name,
@@ -805,6 +830,7 @@ fn handle_template_param_standin(
                 add_lower_bound,
                 bound_equality_classlike,
                 depth + 1,
+                widen_loop_isset_mixed,
             );
 
             if extra_type_union.is_single() {
@@ -862,6 +888,7 @@ fn handle_template_param_standin(
                     add_lower_bound,
                     bound_equality_classlike,
                     depth + 1,
+                    widen_loop_isset_mixed,
                 );
             }
 
@@ -940,6 +967,7 @@ fn handle_template_param_standin(
             add_lower_bound,
             bound_equality_classlike,
             depth + 1,
+            widen_loop_isset_mixed,
         );
 
         if let Some(input_type) = input_type {
@@ -1155,6 +1183,7 @@ fn handle_template_param_class_standin(
     bound_equality_classlike: Option<StrId>,
     depth: usize,
     was_single: bool,
+    widen_loop_isset_mixed: bool,
 ) -> Vec<TAtomic> {
     if let TAtomic::TGenericClassname {
         defining_entity,
@@ -1237,6 +1266,7 @@ fn handle_template_param_class_standin(
                 add_lower_bound,
                 bound_equality_classlike,
                 depth,
+                widen_loop_isset_mixed,
             );
 
             atomic_type_as = if as_type_union.is_single() {
@@ -1338,6 +1368,7 @@ fn handle_template_param_type_standin(
     bound_equality_classlike: Option<StrId>,
     depth: usize,
     was_single: bool,
+    widen_loop_isset_mixed: bool,
 ) -> Vec<TAtomic> {
     if let TAtomic::TGenericTypename {
         defining_entity,
@@ -1411,6 +1442,7 @@ fn handle_template_param_type_standin(
                 add_lower_bound,
                 bound_equality_classlike,
                 depth,
+                widen_loop_isset_mixed,
             );
 
             atomic_type_as = if as_type_union.is_single() {